@@ -21,8 +21,8 @@ offsets = [
 scene = pyphyrs.Scene(gravity=(0,0))
 
 # Add anchors
-anchor_left = scene.mass().mass(0).at((-DISTANCE*1.5, 0.0))
-anchor_right = scene.mass().mass(0).at((DISTANCE*1.5, 0.0))
+anchor_left = scene.mass().at((-DISTANCE*1.5, 0.0)).pin()
+anchor_right = scene.mass().at((DISTANCE*1.5, 0.0)).pin()
 
 # Add the masses
 m0 = scene.mass().mass(MASS).at((-DISTANCE*.5 + offsets[0], 0.0))
@@ -30,7 +30,7 @@ offsets = [
 scene = pyphyrs.Scene()
 
 # Add anchor
-anchor = scene.mass().mass(0).at(ORIGIN)
+anchor = scene.mass().at(ORIGIN).pin()
 
 # Add the masses
 m0 = scene.mass().mass(MASS).vel(velocities[0]).at((offsets[0][0] + ORIGIN[0], offsets[0][1]-DISTANCE + ORIGIN[1]))
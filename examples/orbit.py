@@ -10,6 +10,7 @@ K = 2
 DT = 1/60
 SUBSTEPS = 200
 G = 0.2
+EPSILON = 0.05 # Softening, keeps forces finite in close encounters
 
 # Convert time to secons
 TIME = int(TIME / DT)
@@ -27,7 +28,7 @@ m1 = scene.mass().mass(MASS1).at((DISTANCE, 0.0))
 m1.vel((0.0, math.sqrt(G*MASS0/DISTANCE)))
 
 # Add gravity
-scene.add_force(pyphyrs.force.GravityForce([m0, m1], G=G))
+scene.add_force(pyphyrs.force.GravityForce([m0, m1], G=G, epsilon=EPSILON))
 
 # Simulate
 result = scene.simulate(TIME, dt=DT, substeps=SUBSTEPS)
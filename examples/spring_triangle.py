@@ -55,9 +55,9 @@ m2d = (ORIGIN[0] + DDISTANCE * math.cos(math.radians(-90)), ORIGIN[1] + DDISTANC
 
 # Add anchors
 anchors = []
-anchors.append(scene.mass().mass(0).at_angle_pos(m0d, 90+45, DISTANCE, deg=True))
-anchors.append(scene.mass().mass(0).at_angle_pos(m1d, 45, DISTANCE, deg=True))
-anchors.append(scene.mass().mass(0).at_angle_pos(m2d, -90, DISTANCE, deg=True))
+anchors.append(scene.mass().at_angle_pos(m0d, 90+45, DISTANCE, deg=True).pin())
+anchors.append(scene.mass().at_angle_pos(m1d, 45, DISTANCE, deg=True).pin())
+anchors.append(scene.mass().at_angle_pos(m2d, -90, DISTANCE, deg=True).pin())
 
 # Add the springs
 def spring(m1, m2, k):
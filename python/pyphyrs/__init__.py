@@ -17,8 +17,15 @@ def separate_masses(data):
         masses[i]['positions'] = list(map(lambda e: e[i], data['positions']))
         # add velocities
         masses[i]['velocities'] = list(map(lambda e: e[i], data['velocities']))
+        # add unwrapped positions (periodic scenes)
+        if 'unwrapped_positions' in data:
+            masses[i]['unwrapped_positions'] = list(map(lambda e: e[i], data['unwrapped_positions']))
         # add mass
         masses[i]['mass'] = data['masses'][i]
+        # add charge
+        masses[i]['charge'] = data['charges'][i]
+        # add radius
+        masses[i]['radius'] = data['radii'][i]
         # add energy
         masses[i]['energy'] = data['energies']
     return masses
\ No newline at end of file
@@ -65,6 +65,11 @@ def plot_energy_vs_time(results, masses, xlabel='Time (s)', ylabel='Energy (J)',
         plt.figure()
     
     plt.plot(results['time'], results['energies'], label='total')
+    if 'kinetic_energies' in results:
+        plt.plot(results['time'], results['kinetic_energies'], label='kinetic')
+        plt.plot(results['time'], [g + sum(p) for g, p in zip(results['gravity_energies'], results['potential_energies'])], label='potential')
+    if 'dissipated_energies' in results and any(results['dissipated_energies']):
+        plt.plot(results['time'], [e + d for e, d in zip(results['energies'], results['dissipated_energies'])], label='total + dissipated')
 
     plt.xlabel(xlabel)
     plt.ylabel(ylabel)
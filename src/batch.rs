@@ -0,0 +1,81 @@
+// Standard imports
+use std::{sync::{Mutex, atomic::{AtomicUsize, Ordering}}, thread};
+
+// PyO3 imports
+use pyo3::{prelude::*, exceptions::PyValueError};
+
+// Crate imports
+use crate::{Float, scene::Scene, data_collector::InMemoryDataCollector};
+
+// Simulate copies of many scenes in parallel (scenes themselves are left untouched)
+#[pyfunction]
+pub fn batch_simulate(py: Python, scenes: Vec<Py<Scene>>, steps: usize, substeps: usize, dt: Float, threads: Option<usize>) -> PyResult<Vec<InMemoryDataCollector>> {
+    // Default to one thread per core
+    let threads = match threads {
+        Some(0) => return Err(PyValueError::new_err("Number of threads must be positive")),
+        Some(threads) => threads,
+        None => thread::available_parallelism().map_or(1, |threads| threads.get()),
+    };
+
+    // Clone scenes (fails for scenes that need python)
+    let jobs = scenes.iter().enumerate()
+        .map(|(i, scene)| {
+            let scene = scene.borrow(py);
+            let data_collector = scene.data_collector.clone();
+            scene.try_clone()
+                .map(|scene| Mutex::new((scene, data_collector)))
+                .map_err(|err| PyValueError::new_err(format!("Can't simulate scene {} in batch: {}", i, err.value(py))))
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+
+    // Simulate scenes on a pool of threads, each taking the next unsimulated scene
+    py.allow_threads(|| {
+        let next = AtomicUsize::new(0);
+        thread::scope(|scope| {
+            for _ in 0..threads.min(jobs.len()) {
+                scope.spawn(|| loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(job) = jobs.get(i) else { break };
+                    let (scene, data_collector) = &mut *job.lock().unwrap();
+                    scene.simulate_native(data_collector, steps, substeps, dt);
+                });
+            }
+        });
+    });
+
+    // Return data collectors in order of scenes
+    Ok(jobs.into_iter().map(|job| job.into_inner().unwrap().1).collect())
+}
+
+// Test module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{with_python, run_python};
+
+    #[test]
+    fn batch_on_many_threads_matches_sequential_simulation() {
+        with_python(|py| {
+            // Springs of different lengths with masses bouncing off a floor
+            let scenes: Vec<Py<Scene>> = run_python(py, r#"
+scenes = []
+for i in range(6):
+    scene = pyphyrs.Scene(ode="verlet")
+    anchor = scene.mass().pin()
+    mass = scene.mass().at((1.0 + 0.1 * i, 0.0)).radius(0.1)
+    scene.add_force(pyphyrs.force.SpringForce(anchor, mass, 20.0, 1.0))
+    scene.add_collider(pyphyrs.collider.Plane((0, -1.5), (0, 1)))
+    scenes.append(scene)
+"#).get_item("scenes").unwrap().extract().unwrap();
+            let batch = batch_simulate(py, scenes.clone(), 50, 4, 0.01, Some(3)).unwrap();
+
+            // Each scene simulated on its own gives the same data
+            let data = |data_collector: InMemoryDataCollector| Py::new(py, data_collector).unwrap().call_method0(py, "extract_data").unwrap();
+            for (scene, batched) in scenes.iter().zip(batch) {
+                let mut data_collector = scene.borrow(py).data_collector.clone();
+                scene.borrow_mut(py).simulate_native(&mut data_collector, 50, 4, 0.01);
+                assert!(data(data_collector).as_ref(py).eq(data(batched)).unwrap());
+            }
+        });
+    }
+}
\ No newline at end of file
@@ -0,0 +1,314 @@
+// External imports
+use nalgebra::{Vector2, Matrix2};
+
+// PyO3 imports
+use pyo3::{prelude::*, exceptions::{PyTypeError, PyValueError}};
+
+// Crate imports
+use crate::{Float, Vec2};
+
+// Infinite plane (half of the scene behind it is solid)
+#[pyclass(name="Plane")]
+pub struct PlaneCollider {
+    // Native implementation
+    pub(crate) native: Collider,
+}
+
+// Plane implementation
+#[pymethods]
+impl PlaneCollider {
+    // Constructor
+    #[new]
+    fn new(point: Vec2, normal: Vec2, restitution: Option<Float>, friction: Option<Float>) -> PyResult<Self> {
+        let normal = Vector2::new(normal.0, normal.1);
+        if normal.norm() == 0.0 || !normal.norm().is_finite() {
+            return Err(PyValueError::new_err("Normal of a plane must be a non-zero vector"));
+        }
+        Ok(PlaneCollider {
+            native: Collider::new(Shape::Plane { point: Vector2::new(point.0, point.1), normal: normal.normalize() }, restitution, friction)?,
+        })
+    }
+
+    // __doc__ attribute
+    fn __doc__(&self) -> &'static str {
+        "Infinite static wall through a point, masses stay on the side its normal points to"
+    }
+}
+
+// Line segment (thin wall masses bounce off from both sides)
+#[pyclass(name="Segment")]
+pub struct SegmentCollider {
+    // Native implementation
+    pub(crate) native: Collider,
+}
+
+// Segment implementation
+#[pymethods]
+impl SegmentCollider {
+    // Constructor
+    #[new]
+    fn new(a: Vec2, b: Vec2, restitution: Option<Float>, friction: Option<Float>) -> PyResult<Self> {
+        let (a, b) = (Vector2::new(a.0, a.1), Vector2::new(b.0, b.1));
+        if a == b {
+            return Err(PyValueError::new_err("End points of a segment must be different"));
+        }
+        Ok(SegmentCollider {
+            native: Collider::new(Shape::Segment { a, b }, restitution, friction)?,
+        })
+    }
+
+    // __doc__ attribute
+    fn __doc__(&self) -> &'static str {
+        "Static line segment masses bounce off from both sides"
+    }
+}
+
+// Convex polygon (solid)
+#[pyclass(name="Polygon")]
+pub struct PolygonCollider {
+    // Native implementation
+    pub(crate) native: Collider,
+}
+
+// Polygon implementation
+#[pymethods]
+impl PolygonCollider {
+    // Constructor
+    #[new]
+    fn new(vertices: Vec<Vec2>, restitution: Option<Float>, friction: Option<Float>) -> PyResult<Self> {
+        let vertices = convex_polygon(vertices.into_iter().map(|(x, y)| Vector2::new(x, y)).collect())
+            .map_err(PyValueError::new_err)?;
+        Ok(PolygonCollider {
+            native: Collider::new(Shape::Polygon { vertices }, restitution, friction)?,
+        })
+    }
+
+    // __doc__ attribute
+    fn __doc__(&self) -> &'static str {
+        "Static solid convex polygon"
+    }
+}
+
+// Check that vertices make a convex polygon and order them counter-clockwise
+fn convex_polygon(mut vertices: Vec<Vector2<Float>>) -> Result<Vec<Vector2<Float>>, &'static str> {
+    if vertices.len() < 3 {
+        return Err("Polygon needs at least 3 vertices");
+    }
+
+    // Signed area (positive for counter-clockwise order)
+    let n = vertices.len();
+    let cross = |a: Vector2<Float>, b: Vector2<Float>| a.x * b.y - a.y * b.x;
+    let area = (0..n).map(|i| cross(vertices[i], vertices[(i + 1) % n])).sum::<Float>();
+    if area == 0.0 {
+        return Err("Polygon must have a non-zero area");
+    }
+    if area < 0.0 {
+        vertices.reverse();
+    }
+
+    // Every corner has to turn left
+    for i in 0..n {
+        let (a, b, c) = (vertices[i], vertices[(i + 1) % n], vertices[(i + 2) % n]);
+        if cross(b - a, c - b) < 0.0 {
+            return Err("Polygon must be convex");
+        }
+    }
+    Ok(vertices)
+}
+
+// Shape of a static collider
+#[derive(Clone)]
+pub(crate) enum Shape {
+    // Plane through point with unit normal
+    Plane { point: Vector2<Float>, normal: Vector2<Float> },
+    // Segment between two points
+    Segment { a: Vector2<Float>, b: Vector2<Float> },
+    // Convex polygon with counter-clockwise vertices
+    Polygon { vertices: Vec<Vector2<Float>> },
+}
+
+// Native static collider implementation
+#[derive(Clone)]
+pub(crate) struct Collider {
+    pub(crate) shape: Shape,
+
+    // Coefficients of restitution and friction (scene ones are used if not given)
+    pub(crate) restitution: Option<Float>,
+    pub(crate) friction: Option<Float>,
+}
+
+// Native static collider implementation
+impl Collider {
+    // Create collider with checked coefficients
+    fn new(shape: Shape, restitution: Option<Float>, friction: Option<Float>) -> PyResult<Self> {
+        if restitution.is_some_and(|restitution| !(0.0..=1.0).contains(&restitution)) {
+            return Err(PyValueError::new_err("Coefficient of restitution must be between 0 and 1"));
+        }
+        if friction.is_some_and(|friction| !(friction >= 0.0 && friction.is_finite())) {
+            return Err(PyValueError::new_err("Coefficient of friction must be finite and non-negative"));
+        }
+        Ok(Collider { shape, restitution, friction })
+    }
+
+    // Get contact of a mass with given radius which moved from previous position to position during the substep,
+    // returns normal pointing out of the collider and distance the mass has to move along it to touch the surface
+    pub(crate) fn contact(&self, previous: Vector2<Float>, position: Vector2<Float>, radius: Float) -> Option<(Vector2<Float>, Float)> {
+        match &self.shape {
+            Shape::Plane { point, normal } => {
+                let distance = (position - point).dot(normal);
+                (distance < radius).then_some((*normal, radius - distance))
+            },
+            Shape::Segment { a, b } => {
+                // Mass crossed the segment during the substep (points would pass through it otherwise)
+                let normal = Matrix2::new(0.0, -1.0, 1.0, 0.0) * (b - a).normalize();
+                let (before, after) = ((previous - a).dot(&normal), (position - a).dot(&normal));
+                if before != 0.0 && before * after <= 0.0 {
+                    let crossing = previous + (position - previous) * (before / (before - after));
+                    let along = (crossing - a).dot(&(b - a)) / (b - a).norm_squared();
+                    if (0.0..=1.0).contains(&along) {
+                        let side = before.signum();
+                        return Some((normal * side, radius - after * side));
+                    }
+                }
+
+                // Mass overlaps the segment
+                let (normal, distance) = Self::away_from(position - closest_on_segment(*a, *b, position), normal * before.signum())?;
+                (distance < radius).then_some((normal, radius - distance))
+            },
+            Shape::Polygon { vertices } => {
+                // Signed distances to edges (positive outside)
+                let n = vertices.len();
+                let normal = |i: usize| Matrix2::new(0.0, 1.0, -1.0, 0.0) * (vertices[(i + 1) % n] - vertices[i]).normalize();
+                let outside = |p: Vector2<Float>, i: usize| (p - vertices[i]).dot(&normal(i));
+                let (edge, distance) = (0..n).map(|i| (i, outside(position, i))).fold((0, Float::NEG_INFINITY), |max, e| if e.1 > max.1 { e } else { max });
+
+                // Inside, leave through the edge the mass came in through (last entered edge), or the nearest one
+                if distance < 0.0 {
+                    let entered = (0..n).filter(|&i| outside(previous, i) > 0.0)
+                        .map(|i| (i, outside(previous, i) / (outside(previous, i) - outside(position, i))))
+                        .fold(None, |max: Option<(usize, Float)>, e| if max.is_none_or(|max| e.1 > max.1) { Some(e) } else { max });
+                    let edge = entered.map_or(edge, |(i, _)| i);
+                    return Some((normal(edge), radius - outside(position, edge)));
+                }
+
+                // Outside, nearest point of the boundary
+                let closest = (0..n).map(|i| closest_on_segment(vertices[i], vertices[(i + 1) % n], position))
+                    .min_by(|p, q| (position - p).norm_squared().total_cmp(&(position - q).norm_squared()))?;
+                let (normal, distance) = Self::away_from(position - closest, normal(edge))?;
+                (distance < radius).then_some((normal, radius - distance))
+            },
+        }
+    }
+
+    // Get direction and length of offset from the surface (fallback direction is used for zero offset)
+    fn away_from(offset: Vector2<Float>, fallback: Vector2<Float>) -> Option<(Vector2<Float>, Float)> {
+        let distance = offset.norm();
+        if distance > 0.0 {
+            Some((offset / distance, distance))
+        } else if fallback != Vector2::zeros() {
+            Some((fallback, 0.0))
+        } else {
+            None
+        }
+    }
+}
+
+// Get closest point of segment ab to point p
+fn closest_on_segment(a: Vector2<Float>, b: Vector2<Float>, p: Vector2<Float>) -> Vector2<Float> {
+    let ab = b - a;
+    a + ab * ((p - a).dot(&ab) / ab.norm_squared()).clamp(0.0, 1.0)
+}
+
+// Get native implementation of a static collider
+pub(crate) fn native_collider(collider: &PyAny) -> PyResult<Collider> {
+    if let Ok(plane) = collider.extract::<PyRef<PlaneCollider>>() {
+        Ok(plane.native.clone())
+    } else if let Ok(segment) = collider.extract::<PyRef<SegmentCollider>>() {
+        Ok(segment.native.clone())
+    } else if let Ok(polygon) = collider.extract::<PyRef<PolygonCollider>>() {
+        Ok(polygon.native.clone())
+    } else {
+        Err(PyTypeError::new_err(format!("Expected collider, got {}", collider.get_type().name()?)))
+    }
+}
+
+// Create collider module
+#[pymodule]
+pub fn collider(_py: Python, m: &PyModule) -> PyResult<()> {
+    // __doc__ attribute
+    m.add("__doc__", "Module with static colliders masses bounce off")?;
+
+    // Add colliders
+    m.add_class::<PlaneCollider>()?;
+    m.add_class::<SegmentCollider>()?;
+    m.add_class::<PolygonCollider>()?;
+
+    // Return Ok(()) to indicate that initialization was successful
+    Ok(())
+}
+
+// Test module
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collider(shape: Shape) -> Collider {
+        Collider { shape, restitution: None, friction: None }
+    }
+
+    #[test]
+    fn plane_pushes_masses_out_by_their_radius() {
+        let floor = collider(Shape::Plane { point: Vector2::zeros(), normal: Vector2::new(0.0, 1.0) });
+
+        // Below the floor, touching and above it
+        let (normal, depth) = floor.contact(Vector2::new(0.0, 1.0), Vector2::new(2.0, -0.5), 0.25).unwrap();
+        assert_eq!((normal, depth), (Vector2::new(0.0, 1.0), 0.75));
+        assert!(floor.contact(Vector2::zeros(), Vector2::new(0.0, 0.25), 0.25).is_none());
+        assert!(floor.contact(Vector2::zeros(), Vector2::new(0.0, 1.0), 0.25).is_none());
+    }
+
+    #[test]
+    fn segment_stops_points_crossing_it() {
+        let wall = collider(Shape::Segment { a: Vector2::new(1.0, -1.0), b: Vector2::new(1.0, 1.0) });
+
+        // Point passing through the wall is put back on the side it came from
+        let (normal, depth) = wall.contact(Vector2::new(0.5, 0.0), Vector2::new(1.5, 0.5), 0.0).unwrap();
+        assert!((normal - Vector2::new(-1.0, 0.0)).norm() < 1e-6);
+        assert!((depth - 0.5).abs() < 1e-6);
+
+        // Passing beside the wall or staying on one side doesn't touch it
+        assert!(wall.contact(Vector2::new(0.5, 2.0), Vector2::new(1.5, 2.0), 0.0).is_none());
+        assert!(wall.contact(Vector2::new(2.0, 0.0), Vector2::new(1.5, 0.0), 0.0).is_none());
+
+        // Ball touching the end of the wall is pushed away from it
+        let (normal, depth) = wall.contact(Vector2::new(1.0, 2.0), Vector2::new(1.0, 1.5), 1.0).unwrap();
+        assert!((normal - Vector2::new(0.0, 1.0)).norm() < 1e-6);
+        assert!((depth - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn polygon_pushes_masses_out_through_the_edge_they_entered() {
+        // Clockwise square is reordered
+        let vertices = convex_polygon(vec![Vector2::new(0.0, 0.0), Vector2::new(0.0, 2.0), Vector2::new(2.0, 2.0), Vector2::new(2.0, 0.0)]).unwrap();
+        let square = collider(Shape::Polygon { vertices });
+
+        // Point came in from the left, deep enough to be closer to the top
+        let (normal, depth) = square.contact(Vector2::new(-0.5, 1.8), Vector2::new(0.6, 1.8), 0.0).unwrap();
+        assert!((normal - Vector2::new(-1.0, 0.0)).norm() < 1e-6);
+        assert!((depth - 0.6).abs() < 1e-6);
+
+        // Ball near the corner
+        let (normal, depth) = square.contact(Vector2::new(3.0, 3.0), Vector2::new(2.3, 2.4), 1.0).unwrap();
+        assert!((normal - Vector2::new(0.6, 0.8)).norm() < 1e-6);
+        assert!((depth - 0.5).abs() < 1e-6);
+        assert!(square.contact(Vector2::new(3.0, 3.0), Vector2::new(2.7, 2.7), 0.5).is_none());
+    }
+
+    #[test]
+    fn polygon_must_be_convex() {
+        let arrow = [(0.0, 0.0), (2.0, 1.0), (0.0, 2.0), (1.0, 1.0)].map(|(x, y)| Vector2::new(x, y)).to_vec();
+        assert_eq!(convex_polygon(arrow).unwrap_err(), "Polygon must be convex");
+        assert!(convex_polygon(vec![Vector2::zeros(), Vector2::new(1.0, 0.0)]).is_err());
+        assert!(convex_polygon(vec![Vector2::zeros(), Vector2::new(1.0, 0.0), Vector2::new(2.0, 0.0)]).is_err());
+    }
+}
\ No newline at end of file
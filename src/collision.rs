@@ -0,0 +1,320 @@
+// External imports
+use nalgebra::{DVector, Vector2};
+
+// Crate imports
+use crate::{Float, scene::State, force::cell_list::CellList, collider::Collider, constraint::CORRECTION_TOLERANCE};
+
+// Collision between two masses
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct CollisionEvent {
+    // Time of the substep in which the collision happened
+    pub(crate) time: Float,
+
+    // Indices of masses
+    pub(crate) m1: usize,
+    pub(crate) m2: usize,
+
+    // Impulse received by the first mass (second mass receives the opposite one)
+    pub(crate) impulse: Vector2<Float>,
+}
+
+// Collisions of masses with a radius, resolved with impulses after each solver step
+#[derive(Clone)]
+pub(crate) struct Collisions {
+    // Coefficient of restitution (1 is elastic, 0 is perfectly inelastic)
+    pub(crate) restitution: Float,
+    // Coefficient of Coulomb friction between surfaces of the masses
+    pub(crate) friction: Float,
+
+    // Static colliders
+    pub(crate) colliders: Vec<Collider>,
+
+    // Collisions since the last frame
+    pub(crate) events: Vec<CollisionEvent>,
+}
+
+// Default implementation
+impl Default for Collisions {
+    fn default() -> Self {
+        Collisions {
+            restitution: 1.0,
+            friction: 0.0,
+            colliders: Vec::new(),
+            events: Vec::new(),
+        }
+    }
+}
+
+// Collisions implementation
+impl Collisions {
+    // Separate overlapping masses and apply impulses to the approaching ones, then do the same with static colliders
+    // (previous positions are at the start of the substep of length dt), returns kinetic energy lost
+    // and whether any mass was moved by more than drift of the solver (masses resting on each other only jitter)
+    pub(crate) fn resolve(&mut self, state: &mut State, previous: &DVector<Float>, time: Float, dt: Float) -> (Float, bool) {
+        let (lost, moved) = self.resolve_masses(state, time, dt);
+        let (collider_lost, collider_moved) = self.resolve_colliders(state, previous, dt);
+        (lost + collider_lost, moved || collider_moved)
+    }
+
+    // Resolve collisions between masses, returns kinetic energy lost and whether any mass was moved
+    fn resolve_masses(&mut self, state: &mut State, time: Float, dt: Float) -> (Float, bool) {
+        // Only masses with a radius collide
+        let masses = (0..state.radii.len()).filter(|&i| state.radii[i] > 0.0).collect::<Vec<_>>();
+        if masses.len() < 2 {
+            return (0.0, false);
+        }
+
+        // Broadphase, masses closer than the largest diameter share a cell or are in neighbouring ones
+        let diameter = 2.0 * masses.iter().map(|&i| state.radii[i]).fold(0.0, Float::max);
+        let points = masses.iter().map(|&i| state.position(i)).collect::<Vec<_>>();
+        let cells = CellList::new(&points, diameter, state.periodic_domain());
+        let mut pairs = Vec::new();
+        cells.for_each_pair(|i, j| pairs.push((masses[i], masses[j])));
+
+        // Resolve each touching pair
+        let mut lost = 0.0;
+        let mut moved = false;
+        for (m1, m2) in pairs {
+            let (pair_lost, pair_moved) = self.resolve_pair(state, m1, m2, time, dt);
+            lost += pair_lost;
+            moved |= pair_moved;
+        }
+        (lost, moved)
+    }
+
+    // Resolve collision of two masses, returns kinetic energy lost and whether the masses were moved
+    fn resolve_pair(&mut self, state: &mut State, m1: usize, m2: usize, time: Float, dt: Float) -> (Float, bool) {
+        // Masses have to overlap (coincident ones have no normal)
+        let d = state.separation(m1, m2);
+        let distance = d.norm();
+        let contact = state.radii[m1] + state.radii[m2];
+        if distance >= contact || distance == 0.0 {
+            return (0.0, false);
+        }
+
+        // Pinned masses don't move
+        let (w1, w2) = (state.inverse_masses[m1], state.inverse_masses[m2]);
+        let w = w1 + w2;
+        if w == 0.0 {
+            return (0.0, false);
+        }
+
+        // Push masses apart along the normal
+        let normal = d / distance;
+        let overlap = contact - distance;
+        let p1 = state.position(m1) + normal * (overlap * w1 / w);
+        let p2 = state.position(m2) - normal * (overlap * w2 / w);
+        Self::set(&mut state.positions, m1, p1);
+        Self::set(&mut state.positions, m2, p2);
+
+        // Separating masses don't need an impulse
+        let velocity = state.velocity(m1) - state.velocity(m2);
+        let Some(impulse) = Self::impulse(velocity, normal, w, self.restitution, self.friction) else {
+            return (0.0, Self::significant(overlap, 0.0, dt, contact));
+        };
+
+        // Apply impulse
+        let v1 = state.velocity(m1) + impulse * w1;
+        let v2 = state.velocity(m2) - impulse * w2;
+        Self::set(&mut state.velocities, m1, v1);
+        Self::set(&mut state.velocities, m2, v2);
+        self.events.push(CollisionEvent { time, m1, m2, impulse });
+
+        // Kinetic energy change is J . (v1 - v2) + |J|^2 * (w1 + w2) / 2
+        let lost = -(impulse.dot(&velocity) + 0.5 * impulse.norm_squared() * w);
+        (lost, Self::significant(overlap, impulse.norm() * w, dt, contact))
+    }
+
+    // Resolve collisions of masses with static colliders, returns kinetic energy lost and whether any mass was moved
+    fn resolve_colliders(&mut self, state: &mut State, previous: &DVector<Float>, dt: Float) -> (Float, bool) {
+        let mut lost = 0.0;
+        let mut moved = false;
+        for collider in self.colliders.iter() {
+            let restitution = collider.restitution.unwrap_or(self.restitution);
+            let friction = collider.friction.unwrap_or(self.friction);
+            for m in 0..state.inverse_masses.len() {
+                // Pinned masses don't move
+                let w = state.inverse_masses[m];
+                if w == 0.0 {
+                    continue;
+                }
+
+                // Move mass out of the collider
+                let previous = Vector2::new(previous[m * 2], previous[m * 2 + 1]);
+                let Some((normal, depth)) = collider.contact(previous, state.position(m), state.radii[m]) else {
+                    continue;
+                };
+                let position = state.position(m) + normal * depth;
+                Self::set(&mut state.positions, m, position);
+
+                // Bounce off
+                let velocity = state.velocity(m);
+                let mut velocity_change = 0.0;
+                if let Some(impulse) = Self::impulse(velocity, normal, w, restitution, friction) {
+                    Self::set(&mut state.velocities, m, velocity + impulse * w);
+                    lost -= impulse.dot(&velocity) + 0.5 * impulse.norm_squared() * w;
+                    velocity_change = impulse.norm() * w;
+                }
+                moved |= Self::significant(depth, velocity_change, dt, state.radii[m]);
+            }
+        }
+        (lost, moved)
+    }
+
+    // Get impulse of a collision with given relative velocity along normal and inverse mass of the pair
+    // (None for separating masses)
+    fn impulse(velocity: Vector2<Float>, normal: Vector2<Float>, w: Float, restitution: Float, friction: Float) -> Option<Vector2<Float>> {
+        let normal_velocity = velocity.dot(&normal);
+        if normal_velocity >= 0.0 {
+            return None;
+        }
+
+        // Normal impulse given by restitution
+        let normal_impulse = -(1.0 + restitution) * normal_velocity / w;
+        let mut impulse = normal * normal_impulse;
+
+        // Friction impulse against sliding, at most enough to stop it
+        let tangent_velocity = velocity - normal * normal_velocity;
+        let sliding = tangent_velocity.norm();
+        if friction > 0.0 && sliding > 0.0 {
+            impulse -= tangent_velocity * ((friction * normal_impulse).min(sliding / w) / sliding);
+        }
+        Some(impulse)
+    }
+
+    // Whether a correction (push out by depth and velocity change carried over a step of length dt) is larger
+    // than drift of the solver, relative to size of the masses
+    fn significant(depth: Float, velocity_change: Float, dt: Float, size: Float) -> bool {
+        depth.max(velocity_change * dt) > CORRECTION_TOLERANCE * size.max(1.0)
+    }
+
+    // Set vector of a mass
+    fn set(values: &mut DVector<Float>, index: usize, value: Vector2<Float>) {
+        values[index * 2] = value.x;
+        values[index * 2 + 1] = value.y;
+    }
+}
+
+// Test module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collider::Shape;
+
+    // Two balls of radius 0.5 moving along the x axis
+    fn balls(masses: [Float; 2], positions: [Float; 2], velocities: [Float; 2]) -> State {
+        State {
+            positions: DVector::from_vec(vec![positions[0], 0.0, positions[1], 0.0]),
+            velocities: DVector::from_vec(vec![velocities[0], 0.0, velocities[1], 0.0]),
+            accelerations: DVector::zeros(4),
+            masses: DVector::from_vec(masses.to_vec()),
+            inverse_masses: DVector::from_vec(masses.iter().map(|m| if *m == 0.0 { 0.0 } else { 1.0 / m }).collect()),
+            radii: DVector::from_element(2, 0.5),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn elastic_collision_of_equal_masses_exchanges_velocities() {
+        let mut state = balls([1.0, 1.0], [0.0, 0.9], [1.0, 0.0]);
+        let mut collisions = Collisions::default();
+        let previous = state.positions.clone();
+        let (lost, moved) = collisions.resolve(&mut state, &previous, 0.5, 0.01);
+
+        // Masses are separated and swap velocities
+        assert!((state.separation(0, 1).norm() - 1.0).abs() < 1e-6);
+        assert!((state.velocity(0) - Vector2::new(0.0, 0.0)).norm() < 1e-6);
+        assert!((state.velocity(1) - Vector2::new(1.0, 0.0)).norm() < 1e-6);
+        assert!(lost.abs() < 1e-6);
+        assert!(moved);
+
+        // Event is recorded with the impulse on the first mass
+        assert_eq!(collisions.events.len(), 1);
+        let event = collisions.events[0];
+        assert_eq!((event.time, event.m1, event.m2), (0.5, 0, 1));
+        assert!((event.impulse.x.abs() - 1.0).abs() < 1e-6);
+
+        // Separated masses aren't moved again
+        let previous = state.positions.clone();
+        assert_eq!(collisions.resolve(&mut state, &previous, 1.0, 0.01), (0.0, false));
+    }
+
+    #[test]
+    fn inelastic_collision_conserves_momentum_and_loses_energy() {
+        let mut state = balls([1.0, 3.0], [0.0, 0.95], [2.0, -1.0]);
+        let mut collisions = Collisions { restitution: 0.0, ..Default::default() };
+        let kinetic = state.kinetic_energy();
+        let previous = state.positions.clone();
+        let (lost, _) = collisions.resolve(&mut state, &previous, 0.0, 0.01);
+
+        // Masses move together with the velocity of the centre of mass
+        assert!((state.velocity(0) - Vector2::new(-0.25, 0.0)).norm() < 1e-6);
+        assert!((state.velocity(1) - Vector2::new(-0.25, 0.0)).norm() < 1e-6);
+        assert!((kinetic - lost - state.kinetic_energy()).abs() < 1e-5);
+        assert!(lost > 0.0);
+    }
+
+    #[test]
+    fn pinned_mass_reflects_and_friction_stops_sliding() {
+        // Ball hitting a pinned ball at an angle
+        let mut state = balls([1.0, 1.0], [0.0, 0.9], [1.0, 0.0]);
+        state.velocities[1] = 0.2;
+        state.inverse_masses[1] = 0.0;
+        let mut collisions = Collisions { friction: 1.0, ..Default::default() };
+        let previous = state.positions.clone();
+        collisions.resolve(&mut state, &previous, 0.0, 0.01);
+
+        // Only the free ball moves, normal velocity is reversed and sliding stops
+        assert_eq!(state.position(1), Vector2::new(0.9, 0.0));
+        assert_eq!(state.velocity(1), Vector2::zeros());
+        assert!((state.velocity(0) - Vector2::new(-1.0, 0.0)).norm() < 1e-6);
+    }
+
+    #[test]
+    fn mass_bounces_off_floor_with_friction() {
+        let mut state = balls([1.0, 1.0], [0.0, 5.0], [0.0, 0.0]);
+        let previous = DVector::from_vec(vec![0.0, 0.2, 5.0, 0.0]);
+        state.positions[1] = -0.1;
+        state.velocities[0] = 1.0;
+        state.velocities[1] = -2.0;
+        let floor = Collider {
+            shape: Shape::Plane { point: Vector2::zeros(), normal: Vector2::new(0.0, 1.0) },
+            restitution: Some(0.5),
+            friction: None,
+        };
+        let mut collisions = Collisions { friction: 0.1, colliders: vec![floor], ..Default::default() };
+        let kinetic = state.kinetic_energy();
+        let (lost, moved) = collisions.resolve(&mut state, &previous, 0.0, 0.01);
+
+        // Ball rests on the floor, bounces with half the speed and slows down by friction
+        assert!((state.position(0) - Vector2::new(0.0, 0.5)).norm() < 1e-6);
+        assert!((state.velocity(0) - Vector2::new(0.7, 1.0)).norm() < 1e-6);
+        assert!((kinetic - lost - state.kinetic_energy()).abs() < 1e-5);
+        assert!(moved);
+
+        // Ball resting on the floor isn't touched
+        assert_eq!(state.position(1), Vector2::new(5.0, 0.5));
+        assert!(collisions.events.is_empty());
+    }
+
+    #[test]
+    fn resting_contact_only_removes_drift() {
+        // Ball resting on the floor sinks a little during a substep of gravity
+        let mut state = balls([1.0, 1.0], [0.0, 5.0], [0.0, 0.0]);
+        let previous = DVector::from_vec(vec![0.0, 0.5, 5.0, 0.5]);
+        state.positions[1] = 0.5 - 0.5 * 9.81 * 0.001 * 0.001;
+        state.positions[3] = 0.5;
+        state.velocities[1] = -9.81 * 0.001;
+        let floor = Collider {
+            shape: Shape::Plane { point: Vector2::zeros(), normal: Vector2::new(0.0, 1.0) },
+            restitution: None,
+            friction: None,
+        };
+        let mut collisions = Collisions { colliders: vec![floor], ..Default::default() };
+        let (_, moved) = collisions.resolve(&mut state, &previous, 0.0, 0.001);
+
+        // Ball is pushed back, but the correction is too small to count as moving it
+        assert!((state.position(0) - Vector2::new(0.0, 0.5)).norm() < 1e-6);
+        assert!(!moved);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,316 @@
+// External imports
+use nalgebra::{DVector, Vector2};
+
+// PyO3 imports
+use pyo3::{prelude::*, exceptions::{PyTypeError, PyValueError}};
+
+// Crate imports
+use crate::{Float, scene::{MassRef, Scene, State}, force::scene_of};
+
+// Violation of a constraint (relative to its size) below which it counts as satisfied
+pub(crate) const TOLERANCE: Float = 16.0 * Float::EPSILON;
+
+// Correction (relative to size of constraints and colliding masses) below which it only removes drift of the solver
+// and doesn't count as moving the masses
+pub(crate) const CORRECTION_TOLERANCE: Float = 1e-3;
+
+// Constraint enforced on the state after each solver step
+pub(crate) trait Constraint: Send {
+    // Move masses to reduce violation of the constraint, returns impulse needed to do so
+    // (positive when the constraint pulls the masses together)
+    fn project_positions(&self, state: &mut State, dt: Float) -> Float;
+
+    // Change velocities to keep the constraint satisfied, returns impulse needed to do so
+    fn project_velocities(&self, state: &mut State) -> Float;
+
+    // Get violation of the constraint
+    fn violation(&self, state: &State) -> Float;
+
+    // Get size of the constraint (violation is considered small relative to it)
+    fn scale(&self) -> Float;
+
+    // Copy of the constraint for a cloned scene
+    fn box_clone(&self) -> Box<dyn Constraint>;
+}
+
+// Distance constraint implementation (rigid rod between masses)
+#[pyclass(name="Distance")]
+pub struct DistanceConstraint {
+    // Native implementation
+    pub(crate) native: Distance,
+
+    // Scene of the masses
+    pub(crate) scene: Option<Py<Scene>>,
+}
+
+// Distance constraint implementation (python)
+#[pymethods]
+impl DistanceConstraint {
+    // Constructor
+    #[new]
+    fn new(m0: Py<MassRef>, m1: Py<MassRef>, length: Option<Float>, py: Python) -> PyResult<Self> {
+        // Default length is the current distance between masses
+        let length = length.unwrap_or_else(|| {
+            let d = m0.borrow(py).raw_position(py) - m1.borrow(py).raw_position(py);
+            m0.borrow(py).scene.borrow(py).state.minimum_image(d).norm()
+        });
+        if !(length.is_finite() && length >= 0.0) {
+            return Err(PyValueError::new_err("Length of a distance constraint has to be finite and non-negative"));
+        }
+
+        // Create constraint
+        let (scene, indices) = scene_of(py, &[m0, m1])?;
+        Ok(DistanceConstraint {
+            native: Distance {
+                length,
+                m1: indices[0],
+                m2: indices[1],
+            },
+            scene,
+        })
+    }
+
+    // Length getter
+    #[getter(length)]
+    fn get_length(&self) -> Float {
+        self.native.length
+    }
+
+    // Get violation (difference between distance of the masses and length of the constraint)
+    fn get_violation(&self, py: Python) -> Float {
+        self.scene.as_ref().map_or(0.0, |scene| self.native.violation(&scene.borrow(py).state))
+    }
+
+    // __doc__ attribute
+    fn __doc__(&self) -> &'static str {
+        "Keeps masses at a fixed distance, like a massless rigid rod"
+    }
+}
+
+// Native distance constraint implementation
+#[derive(Clone)]
+pub(crate) struct Distance {
+    // Distance between masses
+    length: Float,
+
+    // Indices of masses
+    m1: usize,
+    m2: usize,
+}
+
+// Native distance constraint implementation
+impl Distance {
+    // Get inverse masses of both masses (zero mass objects don't move)
+    fn inverse_masses(&self, state: &State) -> (Float, Float) {
+        (state.inverse_masses[self.m1], state.inverse_masses[self.m2])
+    }
+
+    // Move masses along direction u by an impulse like change of relative quantity
+    fn correct(values: &mut DVector<Float>, (i1, w1): (usize, Float), (i2, w2): (usize, Float), u: Vector2<Float>, change: Float) {
+        let w = w1 + w2;
+        values[i1 * 2] -= u.x * change * w1 / w;
+        values[i1 * 2 + 1] -= u.y * change * w1 / w;
+        values[i2 * 2] += u.x * change * w2 / w;
+        values[i2 * 2 + 1] += u.y * change * w2 / w;
+    }
+}
+
+// Native distance constraint implementation
+impl Constraint for Distance {
+    fn project_positions(&self, state: &mut State, dt: Float) -> Float {
+        let (w1, w2) = self.inverse_masses(state);
+        let d = state.separation(self.m1, self.m2);
+        let r = d.norm();
+        if w1 + w2 == 0.0 || r == 0.0 {
+            return 0.0;
+        }
+        let u = d / r;
+
+        // Move masses along the rod (SHAKE), velocities change by the same amount over the step
+        let error = r - self.length;
+        Self::correct(&mut state.positions, (self.m1, w1), (self.m2, w2), u, error);
+        Self::correct(&mut state.velocities, (self.m1, w1), (self.m2, w2), u, error / dt);
+
+        // J = m_eff * dv
+        error / (dt * (w1 + w2))
+    }
+
+    fn project_velocities(&self, state: &mut State) -> Float {
+        let (w1, w2) = self.inverse_masses(state);
+        let d = state.separation(self.m1, self.m2);
+        let r = d.norm();
+        if w1 + w2 == 0.0 || r == 0.0 {
+            return 0.0;
+        }
+        let u = d / r;
+
+        // Remove relative velocity along the rod (RATTLE)
+        let rate = u.dot(&(state.velocity(self.m1) - state.velocity(self.m2)));
+        Self::correct(&mut state.velocities, (self.m1, w1), (self.m2, w2), u, rate);
+
+        // J = m_eff * dv
+        rate / (w1 + w2)
+    }
+
+    fn violation(&self, state: &State) -> Float {
+        state.separation(self.m1, self.m2).norm() - self.length
+    }
+
+    fn scale(&self) -> Float {
+        self.length.max(1.0)
+    }
+
+    fn box_clone(&self) -> Box<dyn Constraint> {
+        Box::new(self.clone())
+    }
+}
+
+// Enforce constraints after a step of length dt, adds impulses of each constraint to given ones
+// (returns whether any mass was moved by more than drift of the solver)
+pub(crate) fn project(constraints: &[Box<dyn Constraint>], state: &mut State, impulses: &mut [Float], iterations: usize, dt: Float) -> bool {
+    let mut moved = false;
+
+    // Positions (iterated as constraints sharing masses disturb each other)
+    for _ in 0..iterations {
+        for (constraint, impulse) in constraints.iter().zip(impulses.iter_mut()) {
+            moved |= constraint.violation(state).abs() > CORRECTION_TOLERANCE * constraint.scale();
+            *impulse += constraint.project_positions(state, dt);
+        }
+        if constraints.iter().all(|constraint| constraint.violation(state).abs() <= TOLERANCE * constraint.scale()) {
+            break;
+        }
+    }
+
+    // Velocities
+    for _ in 0..iterations {
+        let mut converged = true;
+        for (constraint, impulse) in constraints.iter().zip(impulses.iter_mut()) {
+            let change = constraint.project_velocities(state);
+            *impulse += change;
+            moved |= change.abs() * dt > CORRECTION_TOLERANCE * constraint.scale();
+            converged &= change.abs() * dt <= TOLERANCE * constraint.scale();
+        }
+        if converged {
+            break;
+        }
+    }
+    moved
+}
+
+// Get native implementation of a constraint acting on given scene
+pub(crate) fn native_constraint(constraint: &PyAny, scene: &Py<Scene>) -> PyResult<Box<dyn Constraint>> {
+    if let Ok(distance) = constraint.extract::<PyRef<DistanceConstraint>>() {
+        if distance.scene.as_ref().is_some_and(|owner| !owner.is(scene)) {
+            return Err(PyValueError::new_err("Constraint acts on masses of another scene"));
+        }
+        Ok(Box::new(distance.native.clone()))
+    } else {
+        Err(PyTypeError::new_err(format!("Expected constraint, got {}", constraint.get_type().name()?)))
+    }
+}
+
+// Violations and reaction forces of constraints in a frame
+#[derive(Clone, Default)]
+pub(crate) struct ConstraintReport {
+    // Violations at the end of the frame
+    pub(crate) violations: Vec<Float>,
+    // Reaction forces averaged over the frame (positive when pulling masses together)
+    pub(crate) forces: Vec<Float>,
+}
+
+// Create constraint module
+#[pymodule]
+pub fn constraint(_py: Python, m: &PyModule) -> PyResult<()> {
+    // __doc__ attribute
+    m.add("__doc__", "Module with constraints enforced after each step of the solver")?;
+
+    // Add constraints
+    m.add_class::<DistanceConstraint>()?;
+
+    // Return Ok(()) to indicate that initialization was successful
+    Ok(())
+}
+
+// Test module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{with_python, run_python, test_state};
+    use crate::solvers::{ODESolver, VerletODE};
+
+    // Pendulum hanging from a fixed anchor at the origin
+    fn pendulum(angle: Float, length: Float) -> State {
+        test_state(&[0.0, 2.0], &[0.0, 0.0, length * angle.sin(), -length * angle.cos()], &[0.0; 4])
+    }
+
+    #[test]
+    fn distance_constraint_keeps_pendulum_length_and_reports_tension() {
+        let (g, length, angle): (Float, Float, Float) = (9.81, 1.5, 0.5);
+        let rod: Vec<Box<dyn Constraint>> = vec![Box::new(Distance { length, m1: 0, m2: 1 })];
+        let mut state = pendulum(angle, length);
+
+        // Swing down to the lowest point
+        let mut solver = VerletODE;
+        let dt = 0.001;
+        let mut max_violation: Float = 0.0;
+        let mut tension = 0.0;
+        while state.positions[2] > 0.0 {
+            let mut positions = state.positions.clone();
+            let mut velocities = state.velocities.clone();
+            let mut accelerations = DVector::from_vec(vec![0.0, 0.0, 0.0, -g]);
+            solver.solve(dt, &mut positions, &mut velocities, &mut accelerations, &mut |_, _, a| a.copy_from_slice(&[0.0, 0.0, 0.0, -g]));
+            state.positions = positions;
+            state.velocities = velocities;
+
+            let mut impulses = [0.0];
+            project(&rod, &mut state, &mut impulses, 20, dt);
+            tension = impulses[0] / dt;
+            max_violation = max_violation.max(rod[0].violation(&state).abs());
+        }
+
+        // Rod doesn't stretch and masses move along the circle
+        assert!(max_violation < 1e-5, "violation {}", max_violation);
+        assert!(state.velocities[3].abs() < 0.05);
+
+        // Tension at the bottom T = m * (g + v^2 / L), with v^2 = 2 * g * L * (1 - cos(angle))
+        let expected = 2.0 * g * (1.0 + 2.0 * (1.0 - angle.cos()));
+        assert!((tension - expected).abs() < 0.02 * expected, "tension {} expected {}", tension, expected);
+    }
+
+    #[test]
+    fn distance_constraints_sharing_masses_converge() {
+        // Chain of three masses pulled apart
+        let mut state = test_state(&[1.0, 2.0, 3.0], &[0.0, 0.0, 1.3, 0.0, 2.0, 0.4], &[0.0, 0.0, 1.0, 0.5, -1.0, 0.0]);
+        let chain: Vec<Box<dyn Constraint>> = vec![Box::new(Distance { length: 1.0, m1: 0, m2: 1 }), Box::new(Distance { length: 1.0, m1: 1, m2: 2 })];
+        let momentum = |state: &State| (0..3).map(|i| state.velocity(i) * state.masses[i]).sum::<Vector2<Float>>();
+        let initial_momentum = momentum(&state);
+
+        let mut impulses = [0.0; 2];
+        assert!(project(&chain, &mut state, &mut impulses, 100, 0.01));
+
+        // Both links are satisfied without changing momentum
+        for link in chain.iter() {
+            assert!(link.violation(&state).abs() < 1e-5);
+        }
+        assert!((momentum(&state) - initial_momentum).norm() < 1e-4);
+
+        // Satisfied constraints don't move masses
+        state.positions = DVector::from_vec(vec![0.0, 0.0, 1.0, 0.0, 2.0, 0.0]);
+        state.velocities.fill(0.0);
+        assert!(!project(&chain, &mut state, &mut impulses, 100, 0.01));
+    }
+
+    #[test]
+    fn distance_constraint_length_is_checked() {
+        with_python(|py| {
+            let globals = run_python(py, "scene = pyphyrs.Scene()\nmasses = [scene.mass(), scene.mass().at((1.0, 0.0))]");
+
+            // Negative and non-finite lengths are rejected, zero length is allowed
+            for length in ["-1.0", "float('nan')", "float('inf')"] {
+                let error = py.run(&format!("pyphyrs.constraint.Distance(*masses, {})", length), Some(globals), None).unwrap_err();
+                assert!(error.is_instance_of::<PyValueError>(py));
+            }
+            py.run("pyphyrs.constraint.Distance(*masses, 0.0)", Some(globals), None).unwrap();
+        });
+    }
+}
\ No newline at end of file
@@ -7,7 +7,7 @@ use nalgebra::DVector;
 use pyo3::{prelude::*, types::{PyDict, PyList}, intern};
 
 // Crate imports
-use crate::{scene::{Scene, MassRef}, Float};
+use crate::{scene::{State, Energies, MassRef}, constraint::ConstraintReport, collision::CollisionEvent, Float};
 
 // InMemoryDataCollector implementation
 #[pyclass(name = "InMemoryDataCollector")]
@@ -17,11 +17,33 @@ pub(crate) struct InMemoryDataCollector {
     time: Vec<Float>,
     positions: Vec<DVector<Float>>,
     velocities: Vec<DVector<Float>>,
+    // Positions without wrapping into a periodic domain (if enabled)
+    unwrapped_positions: Vec<DVector<Float>>,
+    // Orientations of rigid bodies
+    angles: Vec<DVector<Float>>,
+    angular_velocities: Vec<DVector<Float>>,
 
     energies: Vec<Float>,
+    kinetic_energies: Vec<Float>,
+    gravity_energies: Vec<Float>,
+    potential_energies: Vec<Vec<Float>>,
+    dissipated_energies: Vec<Float>,
+
+    constraint_violations: Vec<Vec<Float>>,
+    constraint_forces: Vec<Vec<Float>>,
+
+    // Collisions between masses (in order of time)
+    collisions: Vec<CollisionEvent>,
 
     // Static data
     masses: DVector<Float>,
+    charges: DVector<Float>,
+    radii: DVector<Float>,
+    // Indices of masses of rigid bodies
+    bodies: Vec<usize>,
+
+    // Settings
+    store_unwrapped: bool,
 }
 
 // Constructor
@@ -31,24 +53,58 @@ impl InMemoryDataCollector {
             time: Vec::new(),
             positions: Vec::new(),
             velocities: Vec::new(),
+            unwrapped_positions: Vec::new(),
+            angles: Vec::new(),
+            angular_velocities: Vec::new(),
             energies: Vec::new(),
+            kinetic_energies: Vec::new(),
+            gravity_energies: Vec::new(),
+            potential_energies: Vec::new(),
+            dissipated_energies: Vec::new(),
+            constraint_violations: Vec::new(),
+            constraint_forces: Vec::new(),
+            collisions: Vec::new(),
             masses: DVector::zeros(0),
+            charges: DVector::zeros(0),
+            radii: DVector::zeros(0),
+            bodies: Vec::new(),
+            store_unwrapped: false,
         }
     }
 
-    pub(crate) fn collect_frame(&mut self, py: Python, scene: &Py<Scene>, time: Float, energy: Float) {
-        // Borrow scene
-        let scene = scene.borrow(py);
+    // Create collector which also stores unwrapped positions
+    pub(crate) fn with_unwrapped(store_unwrapped: bool) -> Self {
+        Self {
+            store_unwrapped,
+            ..Self::new()
+        }
+    }
 
+    pub(crate) fn collect_frame(&mut self, state: &State, time: Float, energies: &Energies, constraints: &ConstraintReport, collisions: &[CollisionEvent]) {
         // Collect data
         self.time.push(time);
-        self.positions.push(scene.positions.clone());
-        self.velocities.push(scene.velocities.clone());
-        self.energies.push(energy);
+        self.positions.push(state.positions.clone());
+        self.velocities.push(state.velocities.clone());
+        if self.store_unwrapped {
+            self.unwrapped_positions.push(state.unwrapped_positions());
+        }
+        self.angles.push(state.angles.clone());
+        self.angular_velocities.push(state.angular_velocities.clone());
+        self.energies.push(energies.total());
+        self.kinetic_energies.push(energies.kinetic);
+        self.gravity_energies.push(energies.gravity);
+        self.potential_energies.push(energies.potentials.clone());
+        self.dissipated_energies.push(energies.dissipated);
+        self.constraint_violations.push(constraints.violations.clone());
+        self.constraint_forces.push(constraints.forces.clone());
+        self.collisions.extend_from_slice(collisions);
 
         // Set masses if not set yet
         if self.masses.is_empty() {
-            self.masses = scene.masses.clone();
+            self.masses = state.masses.clone();
+            self.charges = state.charges.clone();
+            self.radii = state.radii.clone();
+            self.bodies = state.bodies.clone();
         }
     }
 }
@@ -65,11 +121,20 @@ impl InMemoryDataCollector {
         // Get size of velocities
         let velocities_size = self.velocities.iter().map(|v| v.len() * std::mem::size_of::<Float>()).sum::<usize>();
 
-        // Get size of masses
-        let masses_size = self.masses.len() * std::mem::size_of::<Float>();
+        // Get size of unwrapped positions
+        let unwrapped_size = self.unwrapped_positions.iter().map(|v| v.len() * std::mem::size_of::<Float>()).sum::<usize>();
+
+        // Get size of angles and angular velocities
+        let angles_size = self.angles.iter().chain(self.angular_velocities.iter()).map(|v| v.len() * std::mem::size_of::<Float>()).sum::<usize>();
+
+        // Get size of masses, charges and radii
+        let masses_size = (self.masses.len() + self.charges.len() + self.radii.len()) * std::mem::size_of::<Float>();
+
+        // Get size of collisions
+        let collisions_size = self.collisions.len() * std::mem::size_of::<CollisionEvent>();
 
         // Return total size
-        time_size + positions_size + velocities_size + masses_size
+        time_size + positions_size + velocities_size + unwrapped_size + angles_size + masses_size + collisions_size
     }
 
     pub fn _get_mb_size(&self) -> Float {
@@ -90,19 +155,51 @@ impl InMemoryDataCollector {
         dict.set_item(intern!(py, "time"), self.time.clone())?;
         dict.set_item(intern!(py, "positions"), self.extract_positions(py)?)?;
         dict.set_item(intern!(py, "velocities"), self.extract_velocities(py)?)?;
+        if self.store_unwrapped {
+            dict.set_item(intern!(py, "unwrapped_positions"), self.extract_unwrapped_positions(py)?)?;
+        }
         dict.set_item(intern!(py, "masses"), self.masses.as_slice())?;
+        dict.set_item(intern!(py, "charges"), self.charges.as_slice())?;
+        dict.set_item(intern!(py, "radii"), self.radii.as_slice())?;
+        dict.set_item(intern!(py, "bodies"), self.bodies.clone())?;
+        dict.set_item(intern!(py, "angles"), self.angles.iter().map(|angles| angles.as_slice().to_vec()).collect::<Vec<_>>())?;
+        dict.set_item(intern!(py, "angular_velocities"), self.angular_velocities.iter().map(|velocities| velocities.as_slice().to_vec()).collect::<Vec<_>>())?;
         dict.set_item(intern!(py, "energies"), self.energies.clone())?;
+        dict.set_item(intern!(py, "kinetic_energies"), self.kinetic_energies.clone())?;
+        dict.set_item(intern!(py, "gravity_energies"), self.gravity_energies.clone())?;
+        dict.set_item(intern!(py, "potential_energies"), self.potential_energies.clone())?;
+        dict.set_item(intern!(py, "dissipated_energies"), self.dissipated_energies.clone())?;
+        dict.set_item(intern!(py, "constraint_violations"), self.constraint_violations.clone())?;
+        dict.set_item(intern!(py, "constraint_forces"), self.constraint_forces.clone())?;
+        dict.set_item(intern!(py, "collisions"), self.extract_collisions(py)?)?;
 
         // Return dictionary
         Ok(dict.to_object(py))
     }
 
+    fn extract_collisions(&self, py: Python) -> PyResult<PyObject> {
+        // Create list
+        let list = PyList::empty(py);
+
+        // Add collisions as dictionaries
+        for collision in self.collisions.iter() {
+            let dict = PyDict::new(py);
+            dict.set_item(intern!(py, "time"), collision.time)?;
+            dict.set_item(intern!(py, "pair"), (collision.m1, collision.m2))?;
+            dict.set_item(intern!(py, "impulse"), (collision.impulse.x, collision.impulse.y))?;
+            list.append(dict)?;
+        }
+
+        // Return list
+        Ok(list.to_object(py))
+    }
+
     fn extract_positions(&self, py: Python) -> PyResult<PyObject> {
         // Create list
         let list = PyList::empty(py);
 
         // Add data
-        for (_i, positions) in self.positions.iter().enumerate() {
+        for positions in self.positions.iter() {
             // Collect positions into tuples of (x, y)
             let mut positions = positions.iter();
 
@@ -127,7 +224,7 @@ impl InMemoryDataCollector {
         let list = PyList::empty(py);
 
         // Add data
-        for (_i, velocities) in self.velocities.iter().enumerate() {
+        for velocities in self.velocities.iter() {
             // Collect velocities into tuples of (x, y)
             let mut velocities = velocities.iter();
 
@@ -147,6 +244,31 @@ impl InMemoryDataCollector {
         Ok(list.to_object(py))
     }
 
+    fn extract_unwrapped_positions(&self, py: Python) -> PyResult<PyObject> {
+        // Create list
+        let list = PyList::empty(py);
+
+        // Add data
+        for positions in self.unwrapped_positions.iter() {
+            // Collect positions into tuples of (x, y)
+            let mut positions = positions.iter();
+
+            // Create list
+            let list2 = PyList::empty(py);
+
+            // Add positions
+            for _j in 0..self.masses.len() {
+                list2.append((positions.next().unwrap(), positions.next().unwrap()))?;
+            }
+
+            // Add list to list
+            list.append(list2)?;
+        }
+
+        // Return list
+        Ok(list.to_object(py))
+    }
+
     fn positions_at(&self, py: Python, timestep: usize) -> PyResult<PyObject> {
         // Create list
         let list = PyList::empty(py);
@@ -167,7 +289,7 @@ impl InMemoryDataCollector {
         let list = PyList::empty(py);
 
         // Add data
-        for (_i, positions) in self.positions.iter().enumerate() {
+        for positions in self.positions.iter() {
             // Collect positions into tuples of (x, y)
             let mut positions = positions.iter();
 
@@ -204,7 +326,7 @@ impl InMemoryDataCollector {
         let list = PyList::empty(py);
 
         // Add data
-        for (_i, velocities) in self.velocities.iter().enumerate() {
+        for velocities in self.velocities.iter() {
             // Collect velocities into tuples of (x, y)
             let mut velocities = velocities.iter();
 
@@ -227,9 +349,18 @@ impl InMemoryDataCollector {
 
         // Add data
         dict.set_item(intern!(py, "masses"), self.masses.as_slice())?;
+        dict.set_item(intern!(py, "charges"), self.charges.as_slice())?;
         dict.set_item(intern!(py, "positions"), self.positions_at(py, timestep)?)?;
         dict.set_item(intern!(py, "velocities"), self.velocities_at(py, timestep)?)?;
+        dict.set_item(intern!(py, "angles"), self.angles[timestep].as_slice())?;
+        dict.set_item(intern!(py, "angular_velocities"), self.angular_velocities[timestep].as_slice())?;
         dict.set_item(intern!(py, "energy"), self.energies[timestep])?;
+        dict.set_item(intern!(py, "kinetic_energy"), self.kinetic_energies[timestep])?;
+        dict.set_item(intern!(py, "gravity_energy"), self.gravity_energies[timestep])?;
+        dict.set_item(intern!(py, "potential_energies"), self.potential_energies[timestep].clone())?;
+        dict.set_item(intern!(py, "dissipated_energy"), self.dissipated_energies[timestep])?;
+        dict.set_item(intern!(py, "constraint_violations"), self.constraint_violations[timestep].clone())?;
+        dict.set_item(intern!(py, "constraint_forces"), self.constraint_forces[timestep].clone())?;
 
         // Return dictionary
         Ok(dict.to_object(py))
@@ -241,6 +372,7 @@ impl InMemoryDataCollector {
 
         // Add data
         dict.set_item(intern!(py, "mass"), self.masses[particle.borrow(py).index])?;
+        dict.set_item(intern!(py, "charge"), self.charges[particle.borrow(py).index])?;
         dict.set_item(intern!(py, "position"), self.positions_of(py, particle.borrow(py))?)?;
         dict.set_item(intern!(py, "velocity"), self.velocities_of(py, particle.borrow(py))?)?;
 
@@ -256,21 +388,22 @@ impl InMemoryDataCollector {
         let mut file = File::create(path)?;
 
         // Write headers
-        file.write_all(b"time,ID,m,x,y,dx,dy\n")?;
+        file.write_all(b"time,ID,m,x,y,dx,dy,q\n")?;
 
         // Write data
         for (i, time) in self.time.iter().enumerate() {
             // Write data
             for (j, mass) in self.masses.iter().enumerate() {
                 // Write data
-                file.write_all(format!("{},{},{},{},{},{},{}\n", 
+                file.write_all(format!("{},{},{},{},{},{},{},{}\n", 
                     time, 
                     j, 
                     mass, 
                     self.positions[i][2 * j], 
                     self.positions[i][2 * j + 1], 
                     self.velocities[i][2 * j], 
-                    self.velocities[i][2 * j + 1]
+                    self.velocities[i][2 * j + 1], 
+                    self.charges[j]
                 ).as_bytes())?;
             }
         }
@@ -281,4 +414,30 @@ impl InMemoryDataCollector {
         // Return
         Ok(())
     }
+}
+
+// Test module
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_has_charges_in_the_last_column() {
+        let state = State {
+            positions: DVector::from_vec(vec![1.0, 2.0]),
+            velocities: DVector::from_vec(vec![3.0, 4.0]),
+            masses: DVector::from_vec(vec![5.0]),
+            charges: DVector::from_vec(vec![-6.0]),
+            ..Default::default()
+        };
+        let mut data_collector = InMemoryDataCollector::new();
+        data_collector.collect_frame(&state, 0.5, &Energies::default(), &ConstraintReport::default(), &[]);
+
+        // Columns added later go after the original ones
+        let path = std::env::temp_dir().join(format!("pyphyrs-{}.csv", std::process::id()));
+        data_collector.save_csv(path.to_str().unwrap()).unwrap();
+        let csv = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(csv, "time,ID,m,x,y,dx,dy,q\n0.5,0,5,1,2,3,4,-6\n");
+    }
 }
\ No newline at end of file
@@ -0,0 +1,353 @@
+// External imports
+use nalgebra::Vector2;
+
+// PyO3 imports
+use pyo3::{prelude::*, exceptions::PyValueError};
+
+// Crate imports
+use crate::{Float, scene::{MassRef, Scene, State}};
+
+use super::{ForceGenerator, NativeForce, scene_of};
+
+// Maximum depth of the tree (deeper cells are leaves with many masses, e.g. coincident ones)
+const MAX_DEPTH: usize = 32;
+
+// Barnes-Hut gravity implementation
+#[pyclass(name="BarnesHutGravityForce", extends=super::ForceGenerator)]
+pub struct BarnesHutGravityForce {
+    // Native implementation
+    pub(crate) native: BarnesHutGravity,
+
+    // Scene of the masses
+    pub(crate) scene: Option<Py<Scene>>,
+}
+
+// Barnes-Hut gravity implementation
+#[pymethods]
+impl BarnesHutGravityForce {
+    // Constructor
+    #[new]
+    #[allow(non_snake_case)]
+    fn new(masses: Vec<Py<MassRef>>, G: Option<Float>, theta: Option<Float>, epsilon: Option<Float>, py: Python) -> PyResult<(Self, ForceGenerator)> {
+        // Create gravity (the tree doesn't know about periodic images)
+        let (scene, masses) = scene_of(py, &masses)?;
+        if scene.as_ref().is_some_and(|scene| scene.borrow(py).state.periodic_domain().is_some()) {
+            return Err(PyValueError::new_err("BarnesHutGravityForce doesn't support periodic domains, use GravityForce instead"));
+        }
+        Ok((BarnesHutGravityForce {
+            native: BarnesHutGravity {
+                g: G.unwrap_or(0.0000673), // Same default as GravityForce
+                theta: theta.unwrap_or(0.5),
+                epsilon: epsilon.unwrap_or(0.0),
+                masses,
+            },
+            scene,
+        }, ForceGenerator))
+    }
+
+    // Get energy
+    fn get_energy(&self, py: Python) -> PyResult<Float> {
+        Ok(self.scene.as_ref().map_or(0.0, |scene| self.native.energy(&scene.borrow(py).state)))
+    }
+
+    // Apply force
+    fn apply_force(&self, py: Python) -> PyResult<()> {
+        if let Some(scene) = &self.scene {
+            self.native.apply(&mut scene.borrow_mut(py).state);
+        }
+
+        // Return
+        Ok(())
+    }
+
+    // __doc__ attribute
+    fn __doc__(&self) -> &'static str {
+        "Gravity force generator approximated with a Barnes-Hut quadtree (native implementation)"
+    }
+}
+
+// Native Barnes-Hut gravity implementation
+#[derive(Clone)]
+pub(crate) struct BarnesHutGravity {
+    // Gravitational constant
+    g: Float,
+    // Opening angle (cells smaller than theta * distance are treated as a single mass, 0 is exact)
+    theta: Float,
+    // Plummer softening length
+    epsilon: Float,
+
+    // Indices of masses
+    masses: Vec<usize>,
+}
+
+// Cell of the quadtree
+#[derive(Clone, Default)]
+struct Node {
+    // Total mass and its center
+    mass: Float,
+    center: Vector2<Float>,
+    // Side length of the cell
+    size: Float,
+
+    // Range of masses in the tree order
+    start: usize,
+    end: usize,
+    // Range of children in the node list (empty for leaves)
+    first_child: usize,
+    children: usize,
+}
+
+// Quadtree of masses
+struct QuadTree {
+    nodes: Vec<Node>,
+    // Masses sorted so that each node covers a continuous range
+    order: Vec<usize>,
+    // Position of each mass in the order
+    rank: Vec<usize>,
+}
+
+// Quadtree implementation
+impl QuadTree {
+    // Build tree of given masses
+    fn new(state: &State, masses: &[usize]) -> Self {
+        let mut order = masses.to_vec();
+
+        // Bounding square of all masses
+        let mut min = Vector2::repeat(Float::INFINITY);
+        let mut max = Vector2::repeat(Float::NEG_INFINITY);
+        for &m in masses.iter() {
+            min = min.inf(&state.position(m));
+            max = max.sup(&state.position(m));
+        }
+        let size = (max - min).max().max(Float::EPSILON);
+
+        // Build nodes
+        let mut nodes = vec![Node::default()];
+        nodes[0] = Self::build(state, &mut nodes, &mut order, 0, min, size, 0);
+
+        // Position of each mass in the order
+        let mut rank = vec![0; state.masses.len()];
+        for (i, &m) in order.iter().enumerate() {
+            rank[m] = i;
+        }
+
+        QuadTree { nodes, order, rank }
+    }
+
+    // Build node covering given masses (children are stored next to each other)
+    fn build(state: &State, nodes: &mut Vec<Node>, masses: &mut [usize], start: usize, min: Vector2<Float>, size: Float, depth: usize) -> Node {
+        // Total mass and its center
+        let mass = masses.iter().map(|&m| state.masses[m]).sum::<Float>();
+        let center = if mass > 0.0 {
+            masses.iter().map(|&m| state.position(m) * state.masses[m]).sum::<Vector2<Float>>() / mass
+        } else {
+            min + Vector2::repeat(0.5 * size)
+        };
+        let mut node = Node { mass, center, size, start, end: start + masses.len(), ..Default::default() };
+
+        // Leaves hold a single mass (or many at the maximum depth)
+        if masses.len() <= 1 || depth >= MAX_DEPTH {
+            return node;
+        }
+
+        // Sort masses by quadrant
+        let half = 0.5 * size;
+        let mid = min + Vector2::repeat(half);
+        let quadrant = |m: usize| {
+            let p = state.position(m);
+            (p.x >= mid.x) as usize + 2 * (p.y >= mid.y) as usize
+        };
+        masses.sort_unstable_by_key(|&m| quadrant(m));
+
+        // Reserve children for non-empty quadrants
+        let mut groups = Vec::with_capacity(4);
+        let mut offset = 0;
+        for q in 0..4 {
+            let count = masses[offset..].iter().take_while(|&&m| quadrant(m) == q).count();
+            if count > 0 {
+                groups.push((q, offset, count));
+            }
+            offset += count;
+        }
+        node.first_child = nodes.len();
+        node.children = groups.len();
+        nodes.resize(nodes.len() + groups.len(), Node::default());
+
+        // Build children
+        for (i, (q, offset, count)) in groups.into_iter().enumerate() {
+            let child_min = min + Vector2::new((q % 2) as Float * half, (q / 2) as Float * half);
+            let child = Self::build(state, nodes, &mut masses[offset..offset + count], start + offset, child_min, half, depth + 1);
+            nodes[node.first_child + i] = child;
+        }
+        node
+    }
+}
+
+// Native Barnes-Hut gravity implementation
+impl BarnesHutGravity {
+    // Get acceleration and potential (per unit mass) at given mass, caused by all other masses in the tree
+    fn field_at(&self, state: &State, tree: &QuadTree, m: usize) -> (Vector2<Float>, Float) {
+        let p = state.position(m);
+        let rank = tree.rank[m];
+        let eps2 = self.epsilon * self.epsilon;
+
+        // Contribution of point mass at given separation
+        let point = |d: Vector2<Float>, mass: Float| {
+            let s2 = d.norm_squared() + eps2;
+            let s = s2.sqrt();
+            (d * (self.g * mass / (s2 * s)), -self.g * mass / s)
+        };
+
+        // Traverse tree
+        let mut acceleration = Vector2::zeros();
+        let mut potential = 0.0;
+        let mut stack = vec![0];
+        while let Some(n) = stack.pop() {
+            let node = &tree.nodes[n];
+            if node.mass == 0.0 {
+                continue;
+            }
+            let contains = (node.start..node.end).contains(&rank);
+
+            if node.children == 0 {
+                // Sum masses of the leaf directly
+                for &other in tree.order[node.start..node.end].iter() {
+                    if other != m {
+                        let (a, u) = point(state.position(other) - p, state.masses[other]);
+                        acceleration += a;
+                        potential += u;
+                    }
+                }
+            } else if !contains && node.size * node.size < self.theta * self.theta * (node.center - p).norm_squared() {
+                // Far away cell acts as a single mass
+                let (a, u) = point(node.center - p, node.mass);
+                acceleration += a;
+                potential += u;
+            } else {
+                // Open cell
+                stack.extend(node.first_child..node.first_child + node.children);
+            }
+        }
+        (acceleration, potential)
+    }
+}
+
+// Native Barnes-Hut gravity implementation
+impl NativeForce for BarnesHutGravity {
+    fn apply(&self, state: &mut State) {
+        if self.masses.is_empty() {
+            return;
+        }
+
+        // Pinned and zero mass objects don't move
+        let tree = QuadTree::new(state, &self.masses);
+        for &m in self.masses.iter() {
+            if state.inverse_masses[m] == 0.0 {
+                continue;
+            }
+            let (acceleration, _) = self.field_at(state, &tree, m);
+            state.accelerations[m * 2] += acceleration.x;
+            state.accelerations[m * 2 + 1] += acceleration.y;
+        }
+    }
+
+    fn energy(&self, state: &State) -> Float {
+        if self.masses.is_empty() {
+            return 0.0;
+        }
+
+        // U = 1/2 * sum of m * potential (each pair is counted twice)
+        let tree = QuadTree::new(state, &self.masses);
+        0.5 * self.masses.iter().map(|&m| state.masses[m] * self.field_at(state, &tree, m).1).sum::<Float>()
+    }
+
+    fn box_clone(&self) -> Box<dyn NativeForce> {
+        Box::new(self.clone())
+    }
+}
+
+// Test module
+#[cfg(test)]
+mod tests {
+    use nalgebra::DVector;
+
+    use super::*;
+    use crate::test_utils::test_state;
+
+    // Pseudo-random disc of masses (deterministic)
+    fn disc(n: usize) -> State {
+        let mut seed: u64 = 12345;
+        let mut random = move || {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 40) as Float / (1u64 << 24) as Float
+        };
+        let mut positions = Vec::with_capacity(n * 2);
+        let mut masses = Vec::with_capacity(n);
+        for _ in 0..n {
+            let (r, angle) = (random().sqrt() * 10.0, random() * std::f64::consts::TAU as Float);
+            positions.extend([r * angle.cos(), r * angle.sin()]);
+            masses.push(0.5 + random());
+        }
+        test_state(&masses, &positions, &vec![0.0; n * 2])
+    }
+
+    // Accelerations and energy from direct summation over all pairs
+    fn direct(state: &State, g: Float, epsilon: Float) -> (DVector<Float>, Float) {
+        let n = state.masses.len();
+        let mut accelerations = DVector::zeros(n * 2);
+        let mut energy = 0.0;
+        for i in 0..n {
+            for j in 0..n {
+                if i != j {
+                    let d = state.position(j) - state.position(i);
+                    let s2 = d.norm_squared() + epsilon * epsilon;
+                    let a = d * (g * state.masses[j] / (s2 * s2.sqrt()));
+                    accelerations[i * 2] += a.x;
+                    accelerations[i * 2 + 1] += a.y;
+                    energy -= 0.5 * g * state.masses[i] * state.masses[j] / s2.sqrt();
+                }
+            }
+        }
+        (accelerations, energy)
+    }
+
+    // Accelerations and energy from Barnes-Hut tree
+    fn tree(state: &State, theta: Float, epsilon: Float) -> (DVector<Float>, Float) {
+        let gravity = BarnesHutGravity { g: 1.0, theta, epsilon, masses: (0..state.masses.len()).collect() };
+        let mut state = state.clone();
+        gravity.apply(&mut state);
+        let energy = gravity.energy(&state);
+        (state.accelerations, energy)
+    }
+
+    #[test]
+    fn barnes_hut_without_opening_angle_is_exact() {
+        let state = disc(200);
+        let (expected, expected_energy) = direct(&state, 1.0, 0.1);
+        let (accelerations, energy) = tree(&state, 0.0, 0.1);
+        assert!((accelerations - &expected).norm() < 1e-4 * expected.norm());
+        assert!((energy - expected_energy).abs() < 1e-4 * expected_energy.abs());
+    }
+
+    #[test]
+    fn barnes_hut_matches_direct_sum() {
+        let state = disc(1000);
+        let (expected, expected_energy) = direct(&state, 1.0, 0.1);
+
+        // Relative error of all accelerations and of the energy (monopole approximation)
+        for (theta, tolerance) in [(0.25, 5e-3), (0.5, 2e-2)] {
+            let (accelerations, energy) = tree(&state, theta, 0.1);
+            assert!((accelerations - &expected).norm() < tolerance * expected.norm());
+            assert!((energy - expected_energy).abs() < tolerance * expected_energy.abs());
+        }
+    }
+
+    #[test]
+    fn barnes_hut_handles_coincident_masses() {
+        let mut state = disc(3);
+        state.positions = DVector::from_vec(vec![1.0, 1.0, 1.0, 1.0, 2.0, 1.0]);
+        let (expected, _) = direct(&state, 1.0, 0.1);
+        let (accelerations, _) = tree(&state, 0.5, 0.1);
+        assert!((accelerations - &expected).norm() < 1e-4 * expected.norm());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,196 @@
+// External imports
+use nalgebra::Vector2;
+
+// Crate imports
+use crate::{Float, scene::Domain};
+
+// Neighbouring cells visited from each cell (half of them, so each pair of cells is visited once)
+const NEIGHBOURS: [(i64, i64); 4] = [(1, 0), (1, 1), (0, 1), (-1, 1)];
+
+// Points sorted into square cells, used to find pairs of points closer than the cell size
+pub(crate) struct CellList {
+    // Indices of points sorted by their cell
+    order: Vec<usize>,
+    // Cells with range of their points in order (sorted by cell)
+    cells: Vec<((i64, i64), usize, usize)>,
+
+    // Number of cells along each axis of a periodic domain (neighbours wrap around)
+    periodic: Option<(i64, i64)>,
+}
+
+// Cell list implementation
+impl CellList {
+    pub(crate) fn new(points: &[Vector2<Float>], cell_size: Float, domain: Option<&Domain>) -> Self {
+        // Get cell of each point (periodic domain is split into whole cells at least as big as the cell size,
+        // with less than 3 cells along an axis neighbours on both sides would be the same cell, so one is used)
+        let (origin, cell_size, periodic) = match domain {
+            Some(domain) => {
+                let size = domain.size();
+                let count = |size: Float| match (size / cell_size).floor() as i64 {
+                    count if count >= 3 => count,
+                    _ => 1,
+                };
+                let (nx, ny) = (count(size.x), count(size.y));
+                (domain.min, Vector2::new(size.x / nx as Float, size.y / ny as Float), Some((nx, ny)))
+            },
+            None => (Vector2::zeros(), Vector2::new(cell_size, cell_size), None),
+        };
+        let cell = |p: &Vector2<Float>| {
+            let key = (((p.x - origin.x) / cell_size.x).floor() as i64, ((p.y - origin.y) / cell_size.y).floor() as i64);
+            match periodic {
+                Some((nx, ny)) => (key.0.rem_euclid(nx), key.1.rem_euclid(ny)),
+                None => key,
+            }
+        };
+        let keys = points.iter().map(cell).collect::<Vec<_>>();
+
+        // Sort points by cell (stable, so the order doesn't depend on anything but positions)
+        let mut order = (0..points.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&i| keys[i]);
+
+        // Group points of each cell
+        let mut cells: Vec<((i64, i64), usize, usize)> = Vec::new();
+        for (k, &i) in order.iter().enumerate() {
+            match cells.last_mut() {
+                Some((key, _, end)) if *key == keys[i] => *end = k + 1,
+                _ => cells.push((keys[i], k, k + 1)),
+            }
+        }
+
+        CellList { order, cells, periodic }
+    }
+
+    // Get neighbouring cells visited from a cell (axes of a periodic domain with a single cell collapse,
+    // so offsets which became the same or opposite ones are skipped to visit each pair of cells once)
+    fn neighbours(&self, key: (i64, i64)) -> Vec<(i64, i64)> {
+        let mut offsets: Vec<(i64, i64)> = Vec::with_capacity(NEIGHBOURS.len());
+        for (dx, dy) in NEIGHBOURS {
+            let offset = match self.periodic {
+                Some((nx, ny)) => (if nx == 1 { 0 } else { dx }, if ny == 1 { 0 } else { dy }),
+                None => (dx, dy),
+            };
+            if offset != (0, 0) && !offsets.contains(&offset) && !offsets.contains(&(-offset.0, -offset.1)) {
+                offsets.push(offset);
+            }
+        }
+
+        // Get cells (wrapped around a periodic domain)
+        offsets.into_iter().filter_map(|(dx, dy)| {
+            let (x, y) = (key.0.checked_add(dx)?, key.1.checked_add(dy)?);
+            Some(match self.periodic {
+                Some((nx, ny)) => (x.rem_euclid(nx), y.rem_euclid(ny)),
+                None => (x, y),
+            })
+        }).collect()
+    }
+
+    // Get points of a cell
+    fn cell(&self, key: (i64, i64)) -> &[usize] {
+        match self.cells.binary_search_by_key(&key, |&(key, _, _)| key) {
+            Ok(c) => &self.order[self.cells[c].1..self.cells[c].2],
+            Err(_) => &[],
+        }
+    }
+
+    // Call function for each unordered pair of points in the same or neighbouring cells
+    pub(crate) fn for_each_pair(&self, mut f: impl FnMut(usize, usize)) {
+        for &(key, start, end) in self.cells.iter() {
+            let points = &self.order[start..end];
+
+            // Pairs inside the cell
+            for (k, &i) in points.iter().enumerate() {
+                for &j in points[k + 1..].iter() {
+                    f(i, j);
+                }
+            }
+
+            // Pairs with neighbouring cells
+            for neighbour in self.neighbours(key) {
+                for &j in self.cell(neighbour) {
+                    for &i in points {
+                        f(i, j);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Test module
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cell_list_finds_all_close_pairs_once() {
+        // Pseudo-random points
+        let mut seed: u32 = 12345;
+        let mut random = || {
+            seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+            (seed >> 8) as Float / (1 << 24) as Float * 10.0 - 5.0
+        };
+        let points = (0..300).map(|_| Vector2::new(random(), random())).collect::<Vec<_>>();
+
+        // Close pairs found with the cell list
+        let cutoff = 0.7;
+        let cells = CellList::new(&points, cutoff, None);
+        let mut found = Vec::new();
+        cells.for_each_pair(|i, j| {
+            if (points[i] - points[j]).norm() < cutoff {
+                found.push((i.min(j), i.max(j)));
+            }
+        });
+        found.sort();
+
+        // Close pairs found by checking all of them
+        let mut expected = Vec::new();
+        for i in 0..points.len() {
+            for j in i + 1..points.len() {
+                if (points[i] - points[j]).norm() < cutoff {
+                    expected.push((i, j));
+                }
+            }
+        }
+
+        assert!(!expected.is_empty());
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn periodic_cell_list_finds_pairs_through_boundaries_once() {
+        // Pseudo-random points in a box
+        let mut seed: u32 = 6789;
+        let mut random = |size: Float| {
+            seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+            (seed >> 8) as Float / (1 << 24) as Float * size
+        };
+        let domain = Domain { min: Vector2::new(0.0, 0.0), max: Vector2::new(4.0, 2.5), periodic: true };
+        let points = (0..200).map(|_| Vector2::new(random(4.0), random(2.5))).collect::<Vec<_>>();
+
+        // Nearest image of separation
+        let image = |d: Vector2<Float>| Vector2::new(d.x - 4.0 * (d.x / 4.0).round(), d.y - 2.5 * (d.y / 2.5).round());
+
+        // Cutoffs giving many cells, 2 cells along y (merged into one) and a single cell
+        for cutoff in [0.6, 1.1, 1.3] {
+            let cells = CellList::new(&points, cutoff, Some(&domain));
+            let mut found = Vec::new();
+            cells.for_each_pair(|i, j| {
+                if image(points[i] - points[j]).norm() < cutoff {
+                    found.push((i.min(j), i.max(j)));
+                }
+            });
+            found.sort();
+
+            let mut expected = Vec::new();
+            for i in 0..points.len() {
+                for j in i + 1..points.len() {
+                    if image(points[i] - points[j]).norm() < cutoff {
+                        expected.push((i, j));
+                    }
+                }
+            }
+
+            assert_eq!(found, expected, "cutoff {}", cutoff);
+        }
+    }
+}
\ No newline at end of file
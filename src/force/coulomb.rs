@@ -0,0 +1,204 @@
+// External imports
+use nalgebra::{DMatrix, Matrix2, Vector2};
+
+// PyO3 imports
+use pyo3::prelude::*;
+
+// Crate imports
+use crate::{Float, scene::{MassRef, Scene, State}};
+
+use super::{ForceGenerator, NativeForce, add_pair_jacobian, scene_of};
+
+// Coulomb force implementation
+#[pyclass(name="CoulombForce", extends=super::ForceGenerator)]
+pub struct CoulombForce {
+    // Native implementation
+    pub(crate) native: Coulomb,
+
+    // Scene of the masses
+    pub(crate) scene: Option<Py<Scene>>,
+}
+
+// Coulomb force implementation
+#[pymethods]
+impl CoulombForce {
+    // Constructor
+    #[new]
+    fn new(masses: Vec<Py<MassRef>>, k_e: Option<Float>, epsilon: Option<Float>, py: Python) -> PyResult<(Self, ForceGenerator)> {
+        // Create coulomb force
+        let (scene, masses) = scene_of(py, &masses)?;
+        Ok((CoulombForce {
+            native: Coulomb {
+                k_e: k_e.unwrap_or(8.987552e9), // Coulomb constant in SI units
+                epsilon: epsilon.unwrap_or(0.0), // No softening by default
+                masses,
+            },
+            scene,
+        }, ForceGenerator))
+    }
+
+    // Get energy
+    fn get_energy(&self, py: Python) -> PyResult<Float> {
+        Ok(self.scene.as_ref().map_or(0.0, |scene| self.native.energy(&scene.borrow(py).state)))
+    }
+
+    // Apply force
+    fn apply_force(&self, py: Python) -> PyResult<()> {
+        if let Some(scene) = &self.scene {
+            self.native.apply(&mut scene.borrow_mut(py).state);
+        }
+
+        // Return
+        Ok(())
+    }
+
+    // __doc__ attribute
+    fn __doc__(&self) -> &'static str {
+        "Electrostatic force between charged masses with optional softening (native implementation)"
+    }
+}
+
+// Native coulomb force implementation
+#[derive(Clone)]
+pub(crate) struct Coulomb {
+    // Coulomb constant
+    k_e: Float,
+    // Softening length (keeps forces finite in close encounters)
+    epsilon: Float,
+
+    // Indices of masses
+    masses: Vec<usize>,
+}
+
+// Native coulomb force implementation
+impl Coulomb {
+    // Get indices of charged masses (neutral ones don't interact)
+    fn charged(&self, state: &State) -> Vec<usize> {
+        self.masses.iter().copied().filter(|&m| state.charges[m] != 0.0).collect()
+    }
+
+    // Get softened squared distance between masses and their separation (p1 - p2)
+    fn separation(&self, state: &State, m1: usize, m2: usize) -> (Vector2<Float>, Float) {
+        let d = state.separation(m1, m2);
+        (d, d.norm_squared() + self.epsilon * self.epsilon)
+    }
+}
+
+// Call function for each unordered pair of masses
+fn for_each_pair(masses: &[usize], mut f: impl FnMut(usize, usize)) {
+    for (i, &m1) in masses.iter().enumerate() {
+        for &m2 in masses[i + 1..].iter() {
+            f(m1, m2);
+        }
+    }
+}
+
+// Native coulomb force implementation
+impl NativeForce for Coulomb {
+    fn apply(&self, state: &mut State) {
+        // F = k_e * q1 * q2 * d / (r^2 + eps^2)^(3/2) (like charges repel)
+        for_each_pair(&self.charged(state), |m1, m2| {
+            let (d, s2) = self.separation(state, m1, m2);
+            let f = d * (self.k_e * state.charges[m1] * state.charges[m2] / (s2 * s2.sqrt()));
+
+            // Apply force
+            state.apply_force(m1, f);
+            state.apply_force(m2, -f);
+        });
+    }
+
+    fn energy(&self, state: &State) -> Float {
+        // U = k_e * q1 * q2 / sqrt(r^2 + eps^2)
+        let mut energy = 0.0;
+        for_each_pair(&self.charged(state), |m1, m2| {
+            let (_, s2) = self.separation(state, m1, m2);
+            energy += self.k_e * state.charges[m1] * state.charges[m2] / s2.sqrt();
+        });
+        energy
+    }
+
+    fn box_clone(&self) -> Box<dyn NativeForce> {
+        Box::new(self.clone())
+    }
+
+    fn add_jacobian(&self, state: &State, da_dx: &mut DMatrix<Float>, _da_dv: &mut DMatrix<Float>) {
+        // dF/dd = -k_e * q1 * q2 * (I / s^3 - 3 * d * d^T / s^5) with s^2 = r^2 + eps^2
+        for_each_pair(&self.charged(state), |m1, m2| {
+            let (d, s2) = self.separation(state, m1, m2);
+            let s3 = s2 * s2.sqrt();
+            let stiffness = -self.k_e * state.charges[m1] * state.charges[m2] * (Matrix2::identity() / s3 - d * d.transpose() * (3.0 / (s3 * s2)));
+
+            // Add jacobian
+            add_pair_jacobian(da_dx, state, m1, m2, stiffness);
+        });
+    }
+}
+
+// Test module
+#[cfg(test)]
+mod tests {
+    use nalgebra::DVector;
+
+    use super::*;
+    use crate::{solvers::finite_difference_jacobian, test_utils::test_state};
+
+    // State with given masses, charges and positions at rest
+    fn charges(masses: &[Float], charges: &[Float], positions: &[Float]) -> State {
+        State { charges: DVector::from_row_slice(charges), ..test_state(masses, positions, &vec![0.0; positions.len()]) }
+    }
+
+    #[test]
+    fn coulomb_like_charges_repel_and_opposite_attract() {
+        let coulomb = Coulomb { k_e: 1.0, epsilon: 0.0, masses: vec![0, 1] };
+
+        // F = k_e * q1 * q2 / r^2 = 1.5, U = k_e * q1 * q2 / r = 3
+        let mut state = charges(&[2.0, 3.0], &[2.0, 3.0], &[0.0, 0.0, 2.0, 0.0]);
+        coulomb.apply(&mut state);
+        assert!((state.accelerations[0] + 0.75).abs() < 1e-6);
+        assert!((state.accelerations[2] - 0.5).abs() < 1e-6);
+        assert!((coulomb.energy(&state) - 3.0).abs() < 1e-6);
+
+        // Opposite charges attract with negative energy
+        let mut state = charges(&[2.0, 3.0], &[2.0, -3.0], &[0.0, 0.0, 2.0, 0.0]);
+        coulomb.apply(&mut state);
+        assert!((state.accelerations[0] - 0.75).abs() < 1e-6);
+        assert!((coulomb.energy(&state) + 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn coulomb_ignores_neutral_masses() {
+        let coulomb = Coulomb { k_e: 1.0, epsilon: 0.0, masses: vec![0, 1, 2] };
+        let mut state = charges(&[1.0; 3], &[1.0, 0.0, 1.0], &[0.0, 0.0, 0.0, 0.0, 1.0, 0.0]);
+        coulomb.apply(&mut state);
+
+        // Neutral mass on top of a charged one feels nothing
+        assert_eq!(state.accelerations[2], 0.0);
+        assert!((state.accelerations[0] + 1.0).abs() < 1e-6);
+        assert!((coulomb.energy(&state) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn coulomb_jacobian_matches_finite_differences() {
+        let coulomb = Coulomb { k_e: 1.0, epsilon: 0.1, masses: vec![0, 1, 2] };
+        let state = charges(&[1.0, 2.0, 3.0], &[1.0, -2.0, 0.5], &[0.0, 0.0, 1.0, 0.5, -0.5, 1.0]);
+
+        // Analytical jacobian
+        let mut da_dx = DMatrix::zeros(6, 6);
+        let mut da_dv = DMatrix::zeros(6, 6);
+        coulomb.add_jacobian(&state, &mut da_dx, &mut da_dv);
+
+        // Finite difference jacobian
+        let mut scratch = state.clone();
+        let mut expected_da_dx = DMatrix::zeros(6, 6);
+        let mut expected_da_dv = DMatrix::zeros(6, 6);
+        finite_difference_jacobian(&mut |positions, velocities, accelerations| {
+            scratch.positions.copy_from(positions);
+            scratch.velocities.copy_from(velocities);
+            scratch.accelerations.fill(0.0);
+            coulomb.apply(&mut scratch);
+            accelerations.copy_from(&scratch.accelerations);
+        }, &state.positions, &state.velocities, &mut expected_da_dx, &mut expected_da_dv);
+
+        assert!((da_dx - expected_da_dx).amax() < 1e-2);
+    }
+}
\ No newline at end of file
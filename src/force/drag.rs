@@ -0,0 +1,259 @@
+// Standard imports
+use std::sync::Arc;
+
+// External imports
+use nalgebra::{DMatrix, Matrix2, Vector2};
+
+// PyO3 imports
+use pyo3::prelude::*;
+
+// Crate imports
+use crate::{Float, Vec2, scene::{MassRef, Scene, State}};
+
+use super::{ForceGenerator, NativeForce, scene_of};
+
+// Drag implementation
+#[pyclass(name="DragForce", extends=super::ForceGenerator)]
+pub struct DragForce {
+    // Native implementation
+    pub(crate) native: Drag,
+
+    // Scene of the masses
+    pub(crate) scene: Option<Py<Scene>>,
+}
+
+// Drag implementation
+#[pymethods]
+impl DragForce {
+    // Constructor
+    #[new]
+    fn new(masses: Vec<Py<MassRef>>, linear: Option<Float>, quadratic: Option<Float>, fluid_velocity: Option<&PyAny>, py: Python) -> PyResult<(Self, ForceGenerator)> {
+        // Fluid velocity is either constant or a function of position and time
+        let fluid_velocity = match fluid_velocity {
+            None => FluidVelocity::Uniform(Vector2::zeros()),
+            Some(fluid_velocity) if fluid_velocity.is_callable() => FluidVelocity::Function(python_fluid_velocity(fluid_velocity.into())),
+            Some(fluid_velocity) => {
+                let fluid_velocity = fluid_velocity.extract::<Vec2>()?;
+                FluidVelocity::Uniform(Vector2::new(fluid_velocity.0, fluid_velocity.1))
+            },
+        };
+
+        // Create drag
+        let (scene, masses) = scene_of(py, &masses)?;
+        Ok((DragForce {
+            native: Drag {
+                linear: linear.unwrap_or(0.0),
+                quadratic: quadratic.unwrap_or(0.0),
+                fluid_velocity,
+                masses,
+            },
+            scene,
+        }, ForceGenerator))
+    }
+
+    // Get energy (drag has no potential)
+    fn get_energy(&self) -> PyResult<Float> {
+        Ok(0.0)
+    }
+
+    // Apply force
+    fn apply_force(&self, py: Python) -> PyResult<()> {
+        if let Some(scene) = &self.scene {
+            self.native.apply(&mut scene.borrow_mut(py).state);
+        }
+
+        // Return
+        Ok(())
+    }
+
+    // __doc__ attribute
+    fn __doc__(&self) -> &'static str {
+        "Linear and quadratic drag in a fluid with given velocity, constant or f(position, time) -> (vx, vy) (native implementation)"
+    }
+}
+
+// Function giving fluid velocities at positions for given time (solvers don't tell stages of a substep apart,
+// so all of them get time at the start of the substep)
+type FluidVelocityFn = dyn Fn(&[Vector2<Float>], Float) -> Vec<Vector2<Float>> + Send + Sync;
+
+// Velocity of the fluid
+#[derive(Clone)]
+pub(crate) enum FluidVelocity {
+    Uniform(Vector2<Float>),
+    Function(Arc<FluidVelocityFn>),
+}
+
+// Wrap python function (position, time) -> velocity, reacquiring the GIL only to call it
+fn python_fluid_velocity(function: PyObject) -> Arc<FluidVelocityFn> {
+    Arc::new(move |positions, time| Python::with_gil(|py| {
+        positions.iter().map(|p| {
+            let result = function.call1(py, ((p.x, p.y), time)).and_then(|result| result.extract::<Vec2>(py));
+            match result {
+                Ok(velocity) => Vector2::new(velocity.0, velocity.1),
+                Err(err) => panic!("Error while evaluating fluid velocity: {:?}", err),
+            }
+        }).collect()
+    }))
+}
+
+// Native drag implementation
+#[derive(Clone)]
+pub(crate) struct Drag {
+    // Coefficient of force proportional to velocity
+    linear: Float,
+    // Coefficient of force proportional to velocity squared
+    quadratic: Float,
+    fluid_velocity: FluidVelocity,
+
+    // Indices of masses
+    masses: Vec<usize>,
+}
+
+// Native drag implementation
+impl Drag {
+    // Get velocities of masses relative to the fluid
+    fn relative_velocities(&self, state: &State) -> Vec<Vector2<Float>> {
+        match &self.fluid_velocity {
+            FluidVelocity::Uniform(fluid_velocity) => self.masses.iter().map(|&m| state.velocity(m) - fluid_velocity).collect(),
+            FluidVelocity::Function(function) => {
+                let positions = self.masses.iter().map(|&m| state.position(m)).collect::<Vec<_>>();
+                self.masses.iter().zip(function(&positions, state.time)).map(|(&m, fluid_velocity)| state.velocity(m) - fluid_velocity).collect()
+            },
+        }
+    }
+}
+
+// Native drag implementation
+impl NativeForce for Drag {
+    fn apply(&self, state: &mut State) {
+        for (&m, w) in self.masses.iter().zip(self.relative_velocities(state)) {
+            // F = -b * w - c * |w| * w
+            let force = -w * (self.linear + self.quadratic * w.norm());
+            state.apply_force(m, force);
+        }
+    }
+
+    fn energy(&self, _state: &State) -> Float {
+        0.0
+    }
+
+    fn dissipation(&self, state: &State) -> Float {
+        // P = b * |w|^2 + c * |w|^3 (pinned masses don't move, so drag does no work on them)
+        self.masses.iter().zip(self.relative_velocities(state)).filter(|(&m, _)| state.inverse_masses[m] != 0.0).map(|(_, w)| {
+            let speed = w.norm();
+            speed * speed * (self.linear + self.quadratic * speed)
+        }).sum()
+    }
+
+    fn box_clone(&self) -> Box<dyn NativeForce> {
+        Box::new(self.clone())
+    }
+
+    fn add_jacobian(&self, state: &State, _da_dx: &mut DMatrix<Float>, da_dv: &mut DMatrix<Float>) {
+        // Gradient of the fluid velocity is neglected
+        for (&m, w) in self.masses.iter().zip(self.relative_velocities(state)) {
+            let inverse = state.inverse_masses[m];
+            if inverse == 0.0 {
+                continue;
+            }
+
+            // dF/dw = -b * I - c * (|w| * I + w * w^T / |w|)
+            let speed = w.norm();
+            let mut jacobian = -Matrix2::identity() * (self.linear + self.quadratic * speed);
+            if speed > 0.0 {
+                jacobian -= w * w.transpose() * (self.quadratic / speed);
+            }
+            let mut block = da_dv.fixed_view_mut::<2, 2>(m * 2, m * 2);
+            block += jacobian * inverse;
+        }
+    }
+}
+
+// Test module
+#[cfg(test)]
+mod tests {
+    use nalgebra::DVector;
+
+    use super::*;
+    use crate::{solvers::{ODESolver, RK4, finite_difference_jacobian}, test_utils::test_state};
+
+    // Single mass moving with given velocity
+    fn projectile(velocity: Vec2) -> State {
+        test_state(&[2.0], &[0.0, 0.0], &[velocity.0, velocity.1])
+    }
+
+    #[test]
+    fn drag_reaches_terminal_velocity() {
+        // Falling mass with m * g = c * v^2 at terminal velocity 4
+        let (g, mass, quadratic): (Float, Float, Float) = (9.81, 2.0, 9.81 * 2.0 / 16.0);
+        let drag = Drag { linear: 0.0, quadratic, fluid_velocity: FluidVelocity::Uniform(Vector2::zeros()), masses: vec![0] };
+        let mut state = projectile((0.0, 0.0));
+
+        // Fall for 10 seconds
+        let mut solver = RK4;
+        for _ in 0..1000 {
+            let mut positions = state.positions.clone();
+            let mut velocities = state.velocities.clone();
+            let mut accelerations = DVector::zeros(2);
+            let mut evaluate = |p: &DVector<Float>, v: &DVector<Float>, a: &mut DVector<Float>| {
+                state.positions.copy_from(p);
+                state.velocities.copy_from(v);
+                state.accelerations = DVector::from_vec(vec![0.0, -g]);
+                drag.apply(&mut state);
+                a.copy_from(&state.accelerations);
+            };
+            evaluate(&positions, &velocities, &mut accelerations);
+            solver.solve(0.01, &mut positions, &mut velocities, &mut accelerations, &mut evaluate);
+            state.positions = positions;
+            state.velocities = velocities;
+        }
+        assert!((state.velocities[1] + (mass * g / quadratic).sqrt()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn drag_acts_against_relative_velocity() {
+        // Mass at rest in a wind blowing to the right
+        let drag = Drag { linear: 0.5, quadratic: 0.25, fluid_velocity: FluidVelocity::Uniform(Vector2::new(2.0, 0.0)), masses: vec![0] };
+        let mut state = projectile((0.0, 0.0));
+        drag.apply(&mut state);
+
+        // F = b * 2 + c * 2 * 2 = 2, pushed with the wind
+        assert!((state.accelerations[0] - 1.0).abs() < 1e-6);
+        assert_eq!(state.accelerations[1], 0.0);
+        assert!((drag.dissipation(&state) - 4.0).abs() < 1e-6);
+
+        // Pinned mass isn't pushed and drag does no work on it
+        state.inverse_masses[0] = 0.0;
+        state.accelerations.fill(0.0);
+        drag.apply(&mut state);
+        assert_eq!(state.accelerations, DVector::zeros(2));
+        assert_eq!(drag.dissipation(&state), 0.0);
+    }
+
+    #[test]
+    fn drag_jacobian_matches_finite_differences() {
+        let drag = Drag { linear: 0.3, quadratic: 0.7, fluid_velocity: FluidVelocity::Uniform(Vector2::new(0.5, -0.2)), masses: vec![0] };
+        let mut state = projectile((1.5, 2.0));
+
+        // Analytical jacobian
+        let mut da_dx = DMatrix::zeros(2, 2);
+        let mut da_dv = DMatrix::zeros(2, 2);
+        drag.add_jacobian(&state, &mut da_dx, &mut da_dv);
+
+        // Finite differences
+        let mut fd_da_dx = DMatrix::zeros(2, 2);
+        let mut fd_da_dv = DMatrix::zeros(2, 2);
+        let (positions, velocities) = (state.positions.clone(), state.velocities.clone());
+        finite_difference_jacobian(&mut |p, v, a| {
+            state.positions.copy_from(p);
+            state.velocities.copy_from(v);
+            state.accelerations.fill(0.0);
+            drag.apply(&mut state);
+            a.copy_from(&state.accelerations);
+        }, &positions, &velocities, &mut fd_da_dx, &mut fd_da_dv);
+
+        // Compare
+        assert!((da_dv - fd_da_dv).amax() < 1e-2);
+        assert_eq!(fd_da_dx, DMatrix::zeros(2, 2));
+    }
+}
\ No newline at end of file
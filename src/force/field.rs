@@ -0,0 +1,180 @@
+// External imports
+use nalgebra::{DMatrix, Vector2};
+
+// PyO3 imports
+use pyo3::prelude::*;
+
+// Crate imports
+use crate::{Float, Vec2, scene::{MassRef, Scene, State}};
+
+use super::{ForceGenerator, NativeForce, scene_of};
+
+// Uniform electromagnetic field implementation
+#[pyclass(name="UniformFieldForce", extends=super::ForceGenerator)]
+pub struct UniformFieldForce {
+    // Native implementation
+    pub(crate) native: UniformField,
+
+    // Scene of the masses
+    pub(crate) scene: Option<Py<Scene>>,
+}
+
+// Uniform electromagnetic field implementation
+#[pymethods]
+impl UniformFieldForce {
+    // Constructor
+    #[new]
+    #[allow(non_snake_case)]
+    fn new(masses: Vec<Py<MassRef>>, E: Option<Vec2>, B: Option<Float>, py: Python) -> PyResult<(Self, ForceGenerator)> {
+        // Create field
+        let E = E.unwrap_or((0.0, 0.0));
+        let (scene, masses) = scene_of(py, &masses)?;
+        Ok((UniformFieldForce {
+            native: UniformField {
+                electric: Vector2::new(E.0, E.1),
+                magnetic: B.unwrap_or(0.0),
+                masses,
+            },
+            scene,
+        }, ForceGenerator))
+    }
+
+    // Get energy
+    fn get_energy(&self, py: Python) -> PyResult<Float> {
+        Ok(self.scene.as_ref().map_or(0.0, |scene| self.native.energy(&scene.borrow(py).state)))
+    }
+
+    // Apply force
+    fn apply_force(&self, py: Python) -> PyResult<()> {
+        if let Some(scene) = &self.scene {
+            self.native.apply(&mut scene.borrow_mut(py).state);
+        }
+
+        // Return
+        Ok(())
+    }
+
+    // __doc__ attribute
+    fn __doc__(&self) -> &'static str {
+        "Uniform electric and magnetic field force generator (native implementation)"
+    }
+}
+
+// Native uniform field implementation
+#[derive(Clone)]
+pub(crate) struct UniformField {
+    // Electric field
+    electric: Vector2<Float>,
+    // Magnetic field (perpendicular to the plane)
+    magnetic: Float,
+
+    // Indices of masses
+    masses: Vec<usize>,
+}
+
+// Native uniform field implementation
+impl NativeForce for UniformField {
+    fn apply(&self, state: &mut State) {
+        for &m in self.masses.iter() {
+            // Skip neutral masses
+            let q = state.charges[m];
+            if q == 0.0 {
+                continue;
+            }
+
+            // F = q * (E + v x B)
+            let v = state.velocity(m);
+            let force = q * (self.electric + Vector2::new(v.y, -v.x) * self.magnetic);
+
+            // Apply force
+            state.apply_force(m, force);
+            state.magnetic_fields[m] += self.magnetic;
+        }
+    }
+
+    fn energy(&self, state: &State) -> Float {
+        // U = -q * E . x (magnetic field does no work)
+        self.masses.iter().map(|&m| -state.charges[m] * self.electric.dot(&state.position(m))).sum::<Float>()
+    }
+
+    fn box_clone(&self) -> Box<dyn NativeForce> {
+        Box::new(self.clone())
+    }
+
+    fn add_jacobian(&self, state: &State, _da_dx: &mut DMatrix<Float>, da_dv: &mut DMatrix<Float>) {
+        // Electric part is constant
+        for &m in self.masses.iter() {
+            let inverse = state.inverse_masses[m];
+            if inverse == 0.0 {
+                continue;
+            }
+
+            // a = q / m * B * (vy, -vx)
+            let w = state.charges[m] * self.magnetic * inverse;
+            da_dv[(m * 2, m * 2 + 1)] += w;
+            da_dv[(m * 2 + 1, m * 2)] -= w;
+        }
+    }
+}
+
+// Electromagnetic field defined by a python function
+#[pyclass(name="FieldForce", extends=super::ForceGenerator)]
+pub struct FieldForce {
+    // Function (x, y) -> ((Ex, Ey), B)
+    field: PyObject,
+
+    // Masses
+    masses: Vec<Py<MassRef>>,
+}
+
+// Python defined field implementation
+#[pymethods]
+impl FieldForce {
+    // Constructor
+    #[new]
+    fn new(masses: Vec<Py<MassRef>>, field: PyObject) -> (Self, ForceGenerator) {
+        // Create field
+        (FieldForce {
+            field,
+            masses,
+        }, ForceGenerator)
+    }
+
+    // Get energy (potential of arbitrary field is unknown)
+    fn get_energy(&self) -> PyResult<Float> {
+        Ok(0.0)
+    }
+
+    // Apply force
+    fn apply_force(&self, py: Python) -> PyResult<()> {
+        for m in self.masses.iter() {
+            let m = m.borrow(py);
+
+            // Skip neutral masses
+            let q = m.raw_charge(py);
+            if q == 0.0 {
+                continue;
+            }
+
+            // Evaluate field at mass position
+            let p = m.raw_position(py);
+            let (electric, magnetic) = self.field.call1(py, (p.x, p.y))?.extract::<(Vec2, Float)>(py)?;
+
+            // F = q * (E + v x B)
+            let v = m.raw_velocity(py);
+            let force = q * (Vector2::new(electric.0, electric.1) + Vector2::new(v.y, -v.x) * magnetic);
+
+            // Apply force
+            m.raw_apply_force(py, force);
+            m.raw_add_magnetic_field(py, magnetic);
+        }
+
+        // Return
+        Ok(())
+    }
+
+    // __doc__ attribute
+    fn __doc__(&self) -> &'static str {
+        "Electric and magnetic field defined by a python function f(x, y) -> ((Ex, Ey), B)"
+    }
+}
\ No newline at end of file
@@ -1,103 +1,52 @@
+// External imports
+use nalgebra::{DMatrix, Matrix2, Vector2};
+
 // PyO3 imports
 use pyo3::prelude::*;
 
 // Crate imports
-use crate::{Float, scene::MassRef};
+use crate::{Float, scene::{MassRef, Scene, State}};
 
-use super::ForceGenerator;
+use super::{ForceGenerator, NativeForce, add_pair_jacobian, scene_of};
 
-// Spring implementation
+// Gravity force implementation
 #[pyclass(name="GravityForce", extends=super::ForceGenerator)]
 pub struct GravityForce {
-    // Gravitational constant
-    g: Float,
+    // Native implementation
+    pub(crate) native: Gravity,
 
-    // Masses
-    masses: Vec<Py<MassRef>>,
+    // Scene of the masses
+    pub(crate) scene: Option<Py<Scene>>,
 }
 
-// Spring implementation
+// Gravity force implementation
 #[pymethods]
 impl GravityForce {
     // Constructor
     #[new]
     #[allow(non_snake_case)]
-    fn new(masses: Vec<Py<MassRef>>, G: Option<Float>, _py: Python) -> (Self, ForceGenerator) {
+    fn new(masses: Vec<Py<MassRef>>, G: Option<Float>, epsilon: Option<Float>, py: Python) -> PyResult<(Self, ForceGenerator)> {
         // Create gravity
-        (GravityForce {
-            g: G.unwrap_or(0.0000673), // Default gravitational constant is 0.0000673 (Sorry, f32 precision :>)
-            masses,
-        }, ForceGenerator::default())
+        let (scene, masses) = scene_of(py, &masses)?;
+        Ok((GravityForce {
+            native: Gravity {
+                g: G.unwrap_or(0.0000673), // Default gravitational constant is 0.0000673 (Sorry, f32 precision :>)
+                epsilon: epsilon.unwrap_or(0.0), // No softening by default
+                masses,
+            },
+            scene,
+        }, ForceGenerator))
     }
 
-    // Get energy (TODO: Fix this)
+    // Get energy
     fn get_energy(&self, py: Python) -> PyResult<Float> {
-        // U = -GMm/r
-
-        // Get masses
-        let masses = &self.masses;
-
-        // Get gravitational constant
-        let g = self.g;
-
-        // Calculate energy
-        let energy = masses.iter().enumerate().map(|(i, m1)| {
-            masses.iter().enumerate().filter(|(j, _)| i != *j).map(|(_, m2)| {
-                let m1 = m1.borrow_mut(py);
-                let m2 = m2.borrow_mut(py);
-
-                // Get positions
-                let p1 = m1.raw_position(py);
-                let p2 = m2.raw_position(py);
-
-                // Get masses
-                let m1 = m1.raw_mass(py);
-                let m2 = m2.raw_mass(py);
-
-                // Calculate energy
-                -g * m1 * m2 / (p1 - p2).norm()
-            }).sum::<Float>()
-        }).sum::<Float>();
-
-        // Return energy
-        Ok(energy)
+        Ok(self.scene.as_ref().map_or(0.0, |scene| self.native.energy(&scene.borrow(py).state)))
     }
-    
+
     // Apply force
     fn apply_force(&self, py: Python) -> PyResult<()> {
-        // F = Gm1m2/r^2
-
-        // Get masses
-        let masses = &self.masses;
-
-        // Get gravitational constant
-        let g = self.g;
-
-        // Apply force
-        for (i, m1) in masses.iter().enumerate() {
-            for (j, m2) in masses.iter().enumerate() {
-                if i != j {
-                    // Get masses
-                    let m1 = m1.borrow_mut(py);
-                    let m2 = m2.borrow_mut(py);
-
-                    // Get positions
-                    let p1 = m1.raw_position(py);
-                    let p2 = m2.raw_position(py);
-
-                    // Get masses
-                    let m1m = m1.raw_mass(py);
-                    let m2m = m2.raw_mass(py);
-
-                    // Calculate force
-                    let f = g * m1m * m2m / (p1 - p2).norm_squared();
-                    let f = (p1 - p2).normalize() * f;
-
-                    // Apply force
-                    m1.raw_apply_force(py, -f);
-                    m2.raw_apply_force(py, f);
-                }
-            }
+        if let Some(scene) = &self.scene {
+            self.native.apply(&mut scene.borrow_mut(py).state);
         }
 
         // Return
@@ -106,6 +55,152 @@ impl GravityForce {
 
     // __doc__ attribute
     fn __doc__(&self) -> &'static str {
-        "Gravity force generator (native implementation)"
+        "Gravity force generator with optional Plummer softening (native implementation)"
+    }
+}
+
+// Native gravity implementation
+#[derive(Clone)]
+pub(crate) struct Gravity {
+    // Gravitational constant
+    g: Float,
+    // Plummer softening length (keeps forces finite in close encounters, without it coincident masses get infinite forces)
+    epsilon: Float,
+
+    // Indices of masses
+    masses: Vec<usize>,
+}
+
+// Native gravity implementation
+impl Gravity {
+    // Call function for each unordered pair of masses (with their indices)
+    fn for_each_pair(&self, mut f: impl FnMut(usize, usize)) {
+        for (i, &m1) in self.masses.iter().enumerate() {
+            for &m2 in self.masses[i + 1..].iter() {
+                f(m1, m2);
+            }
+        }
+    }
+
+    // Get softened squared distance between masses and their separation (p1 - p2)
+    fn separation(&self, state: &State, m1: usize, m2: usize) -> (Vector2<Float>, Float) {
+        let d = state.separation(m1, m2);
+        (d, d.norm_squared() + self.epsilon * self.epsilon)
+    }
+}
+
+// Native gravity implementation
+impl NativeForce for Gravity {
+    fn apply(&self, state: &mut State) {
+        // F = G * m1 * m2 * d / (r^2 + eps^2)^(3/2)
+        self.for_each_pair(|m1, m2| {
+            let (d, s2) = self.separation(state, m1, m2);
+            let f = d * (self.g * state.masses[m1] * state.masses[m2] / (s2 * s2.sqrt()));
+
+            // Apply force
+            state.apply_force(m1, -f);
+            state.apply_force(m2, f);
+        });
+    }
+
+    fn energy(&self, state: &State) -> Float {
+        // U = -G * m1 * m2 / sqrt(r^2 + eps^2)
+        let mut energy = 0.0;
+        self.for_each_pair(|m1, m2| {
+            let (_, s2) = self.separation(state, m1, m2);
+            energy -= self.g * state.masses[m1] * state.masses[m2] / s2.sqrt();
+        });
+        energy
+    }
+
+    fn box_clone(&self) -> Box<dyn NativeForce> {
+        Box::new(self.clone())
+    }
+
+    fn add_jacobian(&self, state: &State, da_dx: &mut DMatrix<Float>, _da_dv: &mut DMatrix<Float>) {
+        // dF/dd = G * m1 * m2 * (I / s^3 - 3 * d * d^T / s^5) with s^2 = r^2 + eps^2
+        self.for_each_pair(|m1, m2| {
+            let (d, s2) = self.separation(state, m1, m2);
+            let s3 = s2 * s2.sqrt();
+            let stiffness = self.g * state.masses[m1] * state.masses[m2] * (Matrix2::identity() / s3 - d * d.transpose() * (3.0 / (s3 * s2)));
+
+            // Add jacobian
+            add_pair_jacobian(da_dx, state, m1, m2, stiffness);
+        });
+    }
+}
+
+// Test module
+#[cfg(test)]
+mod tests {
+    use nalgebra::DVector;
+
+    use super::*;
+    use crate::{solvers::{ODESolver, Yoshida4}, test_utils::test_state};
+
+    #[test]
+    fn gravity_applies_each_pair_once() {
+        let mut state = test_state(&[2.0, 3.0], &[0.0, 0.0, 2.0, 0.0], &[0.0; 4]);
+        let gravity = Gravity { g: 1.0, epsilon: 0.0, masses: vec![0, 1] };
+        gravity.apply(&mut state);
+
+        // F = G * m1 * m2 / r^2 = 1.5
+        assert!((state.accelerations[0] - 0.75).abs() < 1e-6);
+        assert!((state.accelerations[2] + 0.5).abs() < 1e-6);
+
+        // U = -G * m1 * m2 / r
+        assert!((gravity.energy(&state) + 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn gravity_softening_keeps_close_encounters_finite() {
+        let mut state = test_state(&[1.0, 1.0], &[1.0, 1.0, 1.0, 1.0], &[0.0; 4]);
+        let gravity = Gravity { g: 1.0, epsilon: 0.1, masses: vec![0, 1] };
+        gravity.apply(&mut state);
+
+        // No force between coincident masses and finite energy
+        assert_eq!(state.accelerations, DVector::zeros(4));
+        assert!((gravity.energy(&state) + 10.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn gravity_two_body_kepler_orbit() {
+        // Equal masses in the center of mass frame, starting at apoapsis of relative orbit
+        let (r0, v0, mu): (Float, Float, Float) = (1.0, 1.2, 2.0);
+        let mut state = test_state(&[1.0, 1.0], &[-0.5 * r0, 0.0, 0.5 * r0, 0.0], &[0.0, -0.5 * v0, 0.0, 0.5 * v0]);
+        let gravity = Gravity { g: 1.0, epsilon: 0.0, masses: vec![0, 1] };
+        let initial_energy = state.kinetic_energy() + gravity.energy(&state);
+
+        // Kepler's third law: a = 1 / (2 / r - v^2 / mu), T = 2 * pi * sqrt(a^3 / mu)
+        let a = 1.0 / (2.0 / r0 - v0 * v0 / mu);
+        let period = 2.0 * std::f64::consts::PI as Float * (a.powi(3) / mu).sqrt();
+
+        // Simulate one period
+        let mut solver = Yoshida4;
+        let steps = 2000;
+        let dt = period / steps as Float;
+        let mut min_distance = r0;
+        for _ in 0..steps {
+            let mut positions = state.positions.clone();
+            let mut velocities = state.velocities.clone();
+            state.accelerations.fill(0.0);
+            gravity.apply(&mut state);
+            let mut accelerations = state.accelerations.clone();
+            solver.solve(dt, &mut positions, &mut velocities, &mut accelerations, &mut |p, v, acc| {
+                state.positions.copy_from(p);
+                state.velocities.copy_from(v);
+                state.accelerations.fill(0.0);
+                gravity.apply(&mut state);
+                acc.copy_from(&state.accelerations);
+            });
+            state.positions = positions;
+            state.velocities = velocities;
+            min_distance = min_distance.min((state.position(0) - state.position(1)).norm());
+        }
+
+        // Periapsis distance is 2a - r0, bodies are back at the start after one period
+        assert!((min_distance - (2.0 * a - r0)).abs() < 1e-3);
+        assert!((state.position(1) - Vector2::new(0.5 * r0, 0.0)).norm() < 1e-3);
+        assert!((state.kinetic_energy() + gravity.energy(&state) - initial_energy).abs() < 1e-4);
     }
 }
\ No newline at end of file
@@ -1,12 +1,21 @@
+// External imports
+use nalgebra::{DMatrix, Matrix2};
+
 // PyO3 imports
-use pyo3::prelude::*;
+use pyo3::{prelude::*, exceptions::PyValueError};
 
 // Crate imports
-use crate::Float;
+use crate::{Float, scene::{Scene, MassRef, State}, solvers::finite_difference_jacobian};
 
 // Import Forces
 mod spring;
 mod gravity;
+mod barnes_hut;
+mod coulomb;
+mod pair_potential;
+pub(crate) mod cell_list;
+mod field;
+mod drag;
 // Create force generator class
 #[pyclass(name="ForceGenerator", subclass)]
 pub struct ForceGenerator;
@@ -45,6 +54,118 @@ impl Default for ForceGenerator {
     }
 }
 
+// Force generator implemented in rust, applied by the scene directly on its state without python
+pub(crate) trait NativeForce: Send {
+    // Apply forces to the state
+    fn apply(&self, state: &mut State);
+
+    // Get potential energy of the state
+    fn energy(&self, state: &State) -> Float;
+
+    // Get power dissipated in the state (by damping)
+    fn dissipation(&self, _state: &State) -> Float {
+        0.0
+    }
+
+    // Copy of the force generator for a cloned scene
+    fn box_clone(&self) -> Box<dyn NativeForce>;
+
+    // Add jacobians of accelerations with respect to positions and velocities
+    // (approximated with finite differences by default)
+    fn add_jacobian(&self, state: &State, da_dx: &mut DMatrix<Float>, da_dv: &mut DMatrix<Float>) {
+        let n = state.positions.len();
+        let mut scratch = state.clone();
+        let mut force_da_dx = DMatrix::zeros(n, n);
+        let mut force_da_dv = DMatrix::zeros(n, n);
+        finite_difference_jacobian(&mut |positions, velocities, accelerations| {
+            scratch.positions.copy_from(positions);
+            scratch.velocities.copy_from(velocities);
+            scratch.accelerations.fill(0.0);
+            self.apply(&mut scratch);
+            accelerations.copy_from(&scratch.accelerations);
+        }, &state.positions, &state.velocities, &mut force_da_dx, &mut force_da_dv);
+        *da_dx += force_da_dx;
+        *da_dv += force_da_dv;
+    }
+}
+
+// Get native implementation of a force generator acting on given scene (None for python generators)
+pub(crate) fn native_force(py: Python, force_generator: &PyObject, scene: &Py<Scene>) -> Option<Box<dyn NativeForce>> {
+    // Generators created from masses of another scene keep acting on that scene through python
+    let acts_on = |owner: &Option<Py<Scene>>| owner.as_ref().is_none_or(|owner| owner.is(scene));
+
+    let force_generator = force_generator.as_ref(py);
+    if let Ok(spring) = force_generator.extract::<PyRef<spring::SpringForce>>() {
+        if acts_on(&spring.scene) {
+            return Some(Box::new(spring.native.clone()));
+        }
+    } else if let Ok(gravity) = force_generator.extract::<PyRef<gravity::GravityForce>>() {
+        if acts_on(&gravity.scene) {
+            return Some(Box::new(gravity.native.clone()));
+        }
+    } else if let Ok(gravity) = force_generator.extract::<PyRef<barnes_hut::BarnesHutGravityForce>>() {
+        if acts_on(&gravity.scene) {
+            return Some(Box::new(gravity.native.clone()));
+        }
+    } else if let Ok(coulomb) = force_generator.extract::<PyRef<coulomb::CoulombForce>>() {
+        if acts_on(&coulomb.scene) {
+            return Some(Box::new(coulomb.native.clone()));
+        }
+    } else if let Ok(pair_potential) = force_generator.extract::<PyRef<pair_potential::PairPotentialForce>>() {
+        if acts_on(&pair_potential.scene) {
+            return Some(Box::new(pair_potential.native.clone()));
+        }
+    } else if let Ok(drag) = force_generator.extract::<PyRef<drag::DragForce>>() {
+        if acts_on(&drag.scene) {
+            return Some(Box::new(drag.native.clone()));
+        }
+    } else if let Ok(field) = force_generator.extract::<PyRef<field::UniformFieldForce>>() {
+        if acts_on(&field.scene) {
+            return Some(Box::new(field.native.clone()));
+        }
+    }
+    None
+}
+
+// Get scene shared by given masses and their indices
+pub(crate) fn scene_of(py: Python, masses: &[Py<MassRef>]) -> PyResult<(Option<Py<Scene>>, Vec<usize>)> {
+    let mut scene: Option<Py<Scene>> = None;
+    let mut indices = Vec::with_capacity(masses.len());
+    for mass in masses.iter() {
+        let mass = mass.borrow(py);
+        match &scene {
+            Some(scene) if !scene.is(&mass.scene) => {
+                return Err(PyValueError::new_err("All masses of a force generator must belong to the same scene"));
+            },
+            Some(_) => {},
+            None => scene = Some(mass.scene.clone_ref(py)),
+        }
+        indices.push(mass.index);
+    }
+    Ok((scene, indices))
+}
+
+// Add jacobian of a pair force, where m2 feels F(d) and m1 feels -F(d) with d being p1 - p2 (or v1 - v2
+// for jacobians with respect to velocities) and stiffness being dF/dd
+pub(crate) fn add_pair_jacobian(jacobian: &mut DMatrix<Float>, state: &State, i1: usize, i2: usize, stiffness: Matrix2<Float>) {
+    let inverse1 = state.inverse_masses[i1];
+    let inverse2 = state.inverse_masses[i2];
+
+    // Pinned and zero mass objects don't move
+    if inverse1 != 0.0 {
+        let mut block = jacobian.fixed_view_mut::<2, 2>(i1 * 2, i1 * 2);
+        block -= stiffness * inverse1;
+        let mut block = jacobian.fixed_view_mut::<2, 2>(i1 * 2, i2 * 2);
+        block += stiffness * inverse1;
+    }
+    if inverse2 != 0.0 {
+        let mut block = jacobian.fixed_view_mut::<2, 2>(i2 * 2, i1 * 2);
+        block += stiffness * inverse2;
+        let mut block = jacobian.fixed_view_mut::<2, 2>(i2 * 2, i2 * 2);
+        block -= stiffness * inverse2;
+    }
+}
+
 // Create force module
 #[pymodule]
 pub fn force(_py: Python, m: &PyModule) -> PyResult<()> {
@@ -57,6 +178,17 @@ pub fn force(_py: Python, m: &PyModule) -> PyResult<()> {
     // Add forces
     m.add_class::<spring::SpringForce>()?;
     m.add_class::<gravity::GravityForce>()?;
+    m.add_class::<barnes_hut::BarnesHutGravityForce>()?;
+    m.add_class::<coulomb::CoulombForce>()?;
+    m.add_class::<pair_potential::PairPotentialForce>()?;
+    m.add_class::<drag::DragForce>()?;
+    m.add_class::<field::UniformFieldForce>()?;
+    m.add_class::<field::FieldForce>()?;
+
+    // Add pair potentials
+    m.add_class::<pair_potential::LennardJones>()?;
+    m.add_class::<pair_potential::Morse>()?;
+    m.add_class::<pair_potential::TabulatedPotential>()?;
 
     // Return Ok(()) to indicate that initialization was successful
     Ok(())
@@ -0,0 +1,419 @@
+// External imports
+use nalgebra::{DMatrix, Matrix2, Vector2};
+
+// PyO3 imports
+use pyo3::{prelude::*, exceptions::{PyTypeError, PyValueError}};
+
+// Crate imports
+use crate::{Float, scene::{MassRef, Scene, State}};
+
+use super::{ForceGenerator, NativeForce, add_pair_jacobian, scene_of, cell_list::CellList};
+
+// Lennard-Jones potential U = 4 * epsilon * ((sigma / r)^12 - (sigma / r)^6)
+#[pyclass]
+#[derive(Clone)]
+pub struct LennardJones {
+    // Depth of the potential well
+    #[pyo3(get)]
+    epsilon: Float,
+    // Distance at which the potential is zero
+    #[pyo3(get)]
+    sigma: Float,
+}
+
+// Lennard-Jones potential implementation (python)
+#[pymethods]
+impl LennardJones {
+    // Constructor
+    #[new]
+    fn new(epsilon: Option<Float>, sigma: Option<Float>) -> Self {
+        LennardJones {
+            epsilon: epsilon.unwrap_or(1.0),
+            sigma: sigma.unwrap_or(1.0),
+        }
+    }
+}
+
+// Morse potential U = depth * ((1 - exp(-width * (r - r0)))^2 - 1)
+#[pyclass]
+#[derive(Clone)]
+pub struct Morse {
+    // Depth of the potential well
+    #[pyo3(get)]
+    depth: Float,
+    // Inverse width of the potential well
+    #[pyo3(get)]
+    width: Float,
+    // Equilibrium distance
+    #[pyo3(get)]
+    r0: Float,
+}
+
+// Morse potential implementation (python)
+#[pymethods]
+impl Morse {
+    // Constructor
+    #[new]
+    fn new(depth: Option<Float>, width: Option<Float>, r0: Option<Float>) -> Self {
+        Morse {
+            depth: depth.unwrap_or(1.0),
+            width: width.unwrap_or(1.0),
+            r0: r0.unwrap_or(1.0),
+        }
+    }
+}
+
+// Potential given by energies at increasing distances (interpolated with cubic Hermite splines)
+#[pyclass]
+#[derive(Clone)]
+pub struct TabulatedPotential {
+    // Distances
+    #[pyo3(get)]
+    r: Vec<Float>,
+    // Energies at the distances
+    #[pyo3(get)]
+    energy: Vec<Float>,
+
+    // Derivatives of the energy at the distances
+    slopes: Vec<Float>,
+}
+
+// Tabulated potential implementation (python)
+#[pymethods]
+impl TabulatedPotential {
+    // Constructor
+    #[new]
+    fn new(r: Vec<Float>, energy: Vec<Float>) -> PyResult<Self> {
+        Self::from_table(r, energy).map_err(PyValueError::new_err)
+    }
+}
+
+// Tabulated potential implementation
+impl TabulatedPotential {
+    // Create potential from a table (checking it)
+    fn from_table(r: Vec<Float>, energy: Vec<Float>) -> Result<Self, &'static str> {
+        // Check table
+        if r.len() != energy.len() {
+            return Err("Tabulated potential needs the same number of distances and energies");
+        }
+        if r.len() < 2 {
+            return Err("Tabulated potential needs at least 2 points");
+        }
+        if r.windows(2).any(|w| w[0] >= w[1]) {
+            return Err("Distances of tabulated potential have to be increasing");
+        }
+
+        // Slopes from differences of neighbouring points (one sided at the ends)
+        let n = r.len();
+        let slopes = (0..n).map(|k| {
+            let (a, b) = (k.saturating_sub(1), (k + 1).min(n - 1));
+            (energy[b] - energy[a]) / (r[b] - r[a])
+        }).collect();
+
+        Ok(TabulatedPotential { r, energy, slopes })
+    }
+
+    // Get energy and its first and second derivatives (extrapolated linearly outside the table)
+    fn evaluate(&self, r: Float) -> (Float, Float, Float) {
+        // Constant force outside the table
+        let n = self.r.len();
+        if r < self.r[0] {
+            return (self.energy[0] + self.slopes[0] * (r - self.r[0]), self.slopes[0], 0.0);
+        }
+        if r > self.r[n - 1] {
+            return (self.energy[n - 1] + self.slopes[n - 1] * (r - self.r[n - 1]), self.slopes[n - 1], 0.0);
+        }
+
+        // Find segment
+        let k = self.r.partition_point(|&x| x <= r).clamp(1, n - 1) - 1;
+        let h = self.r[k + 1] - self.r[k];
+        let t = (r - self.r[k]) / h;
+        let (y0, y1, m0, m1) = (self.energy[k], self.energy[k + 1], self.slopes[k] * h, self.slopes[k + 1] * h);
+
+        // Hermite basis functions and their derivatives
+        let (t2, t3) = (t * t, t * t * t);
+        let energy = (2.0 * t3 - 3.0 * t2 + 1.0) * y0 + (t3 - 2.0 * t2 + t) * m0 + (-2.0 * t3 + 3.0 * t2) * y1 + (t3 - t2) * m1;
+        let first = ((6.0 * t2 - 6.0 * t) * y0 + (3.0 * t2 - 4.0 * t + 1.0) * m0 + (-6.0 * t2 + 6.0 * t) * y1 + (3.0 * t2 - 2.0 * t) * m1) / h;
+        let second = ((12.0 * t - 6.0) * y0 + (6.0 * t - 4.0) * m0 + (-12.0 * t + 6.0) * y1 + (6.0 * t - 2.0) * m1) / (h * h);
+        (energy, first, second)
+    }
+}
+
+// Pair potential of the native implementation
+#[derive(Clone)]
+pub(crate) enum Potential {
+    LennardJones(LennardJones),
+    Morse(Morse),
+    Tabulated(TabulatedPotential),
+}
+
+// Pair potential implementation
+impl Potential {
+    // Get potential from python object
+    fn from_py(potential: &PyAny) -> PyResult<Self> {
+        if let Ok(potential) = potential.extract::<PyRef<LennardJones>>() {
+            Ok(Potential::LennardJones(potential.clone()))
+        } else if let Ok(potential) = potential.extract::<PyRef<Morse>>() {
+            Ok(Potential::Morse(potential.clone()))
+        } else if let Ok(potential) = potential.extract::<PyRef<TabulatedPotential>>() {
+            Ok(Potential::Tabulated(potential.clone()))
+        } else {
+            Err(PyTypeError::new_err(format!("Expected LennardJones, Morse or TabulatedPotential, got {}", potential.get_type().name()?)))
+        }
+    }
+
+    // Default cutoff radius (where the potential is small enough to be neglected)
+    fn default_cutoff(&self) -> Float {
+        match self {
+            Potential::LennardJones(lj) => 2.5 * lj.sigma,
+            Potential::Morse(morse) => morse.r0 + 5.0 / morse.width,
+            Potential::Tabulated(table) => table.r[table.r.len() - 1],
+        }
+    }
+
+    // Get energy and its first and second derivatives with respect to distance
+    fn evaluate(&self, r: Float) -> (Float, Float, Float) {
+        match self {
+            Potential::LennardJones(LennardJones { epsilon, sigma }) => {
+                let s6 = (sigma / r).powi(6);
+                let s12 = s6 * s6;
+                (4.0 * epsilon * (s12 - s6), 24.0 * epsilon * (s6 - 2.0 * s12) / r, 24.0 * epsilon * (26.0 * s12 - 7.0 * s6) / (r * r))
+            },
+            Potential::Morse(Morse { depth, width, r0 }) => {
+                let e = (-width * (r - r0)).exp();
+                (depth * (e * e - 2.0 * e), 2.0 * width * depth * (e - e * e), 2.0 * width * width * depth * (2.0 * e * e - e))
+            },
+            Potential::Tabulated(table) => table.evaluate(r),
+        }
+    }
+}
+
+// Pair potential force implementation
+#[pyclass(name="PairPotentialForce", extends=super::ForceGenerator)]
+pub struct PairPotentialForce {
+    // Native implementation
+    pub(crate) native: PairPotential,
+
+    // Scene of the masses
+    pub(crate) scene: Option<Py<Scene>>,
+}
+
+// Pair potential force implementation
+#[pymethods]
+impl PairPotentialForce {
+    // Constructor
+    #[new]
+    fn new(masses: Vec<Py<MassRef>>, potential: &PyAny, cutoff: Option<Float>, shift: Option<bool>, py: Python) -> PyResult<(Self, ForceGenerator)> {
+        // Create pair potential
+        let (scene, masses) = scene_of(py, &masses)?;
+        let potential = Potential::from_py(potential)?;
+        let cutoff = cutoff.unwrap_or_else(|| potential.default_cutoff());
+        if cutoff.is_nan() || cutoff <= 0.0 {
+            return Err(PyValueError::new_err("Cutoff has to be positive"));
+        }
+
+        // Shift energy to be zero at the cutoff (forces are not affected)
+        let energy_shift = if shift.unwrap_or(true) && cutoff.is_finite() { potential.evaluate(cutoff).0 } else { 0.0 };
+
+        Ok((PairPotentialForce {
+            native: PairPotential {
+                potential,
+                cutoff,
+                energy_shift,
+                masses,
+            },
+            scene,
+        }, ForceGenerator))
+    }
+
+    // Get energy
+    fn get_energy(&self, py: Python) -> PyResult<Float> {
+        Ok(self.scene.as_ref().map_or(0.0, |scene| self.native.energy(&scene.borrow(py).state)))
+    }
+
+    // Apply force
+    fn apply_force(&self, py: Python) -> PyResult<()> {
+        if let Some(scene) = &self.scene {
+            self.native.apply(&mut scene.borrow_mut(py).state);
+        }
+
+        // Return
+        Ok(())
+    }
+
+    // Cutoff getter
+    #[getter(cutoff)]
+    fn get_cutoff(&self) -> Float {
+        self.native.cutoff
+    }
+
+    // __doc__ attribute
+    fn __doc__(&self) -> &'static str {
+        "Pair potential force with cutoff, using a cell list to find close pairs (native implementation)"
+    }
+}
+
+// Native pair potential implementation
+#[derive(Clone)]
+pub(crate) struct PairPotential {
+    potential: Potential,
+    // Distance beyond which masses don't interact
+    cutoff: Float,
+    // Energy subtracted from each pair (potential at the cutoff)
+    energy_shift: Float,
+
+    // Indices of masses
+    masses: Vec<usize>,
+}
+
+// Native pair potential implementation
+impl PairPotential {
+    // Get pairs of masses closer than the cutoff with their separation (p1 - p2) and distance
+    fn pairs(&self, state: &State) -> Vec<(usize, usize, Vector2<Float>, Float)> {
+        let points = self.masses.iter().map(|&m| state.position(m)).collect::<Vec<_>>();
+        let mut pairs = Vec::new();
+        CellList::new(&points, self.cutoff, state.periodic_domain()).for_each_pair(|i, j| {
+            let d = state.minimum_image(points[i] - points[j]);
+            let r = d.norm();
+
+            // Coincident masses have no direction to push each other in
+            if r < self.cutoff && r > 0.0 {
+                pairs.push((self.masses[i], self.masses[j], d, r));
+            }
+        });
+        pairs
+    }
+}
+
+// Native pair potential implementation
+impl NativeForce for PairPotential {
+    fn apply(&self, state: &mut State) {
+        // F = -dU/dr * d / r
+        for (m1, m2, d, r) in self.pairs(state) {
+            let f = d * (self.potential.evaluate(r).1 / r);
+
+            // Apply force
+            state.apply_force(m1, -f);
+            state.apply_force(m2, f);
+        }
+    }
+
+    fn energy(&self, state: &State) -> Float {
+        self.pairs(state).iter().map(|&(_, _, _, r)| self.potential.evaluate(r).0 - self.energy_shift).sum()
+    }
+
+    fn box_clone(&self) -> Box<dyn NativeForce> {
+        Box::new(self.clone())
+    }
+
+    fn add_jacobian(&self, state: &State, da_dx: &mut DMatrix<Float>, _da_dv: &mut DMatrix<Float>) {
+        // dF/dd = U'' * u * u^T + U' / r * (I - u * u^T) with u = d / r
+        for (m1, m2, d, r) in self.pairs(state) {
+            let (_, first, second) = self.potential.evaluate(r);
+            let uu = d * d.transpose() / (r * r);
+            let stiffness = uu * second + (Matrix2::identity() - uu) * (first / r);
+
+            // Add jacobian
+            add_pair_jacobian(da_dx, state, m1, m2, stiffness);
+        }
+    }
+}
+
+// Test module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solvers::finite_difference_jacobian, test_utils::test_state};
+
+    // State with unit masses at rest at given positions
+    fn particles(positions: &[Float]) -> State {
+        test_state(&vec![1.0; positions.len() / 2], positions, &vec![0.0; positions.len()])
+    }
+
+    // Pair potential acting on all masses
+    fn pair_potential(potential: Potential, cutoff: Float, shift: bool, n: usize) -> PairPotential {
+        let energy_shift = if shift { potential.evaluate(cutoff).0 } else { 0.0 };
+        PairPotential { potential, cutoff, energy_shift, masses: (0..n).collect() }
+    }
+
+    #[test]
+    fn lennard_jones_minimum_and_cutoff() {
+        let lj = Potential::LennardJones(LennardJones { epsilon: 2.0, sigma: 1.0 });
+
+        // No force at the minimum r = 2^(1/6) * sigma, where U = -epsilon
+        let r_min = (2.0 as Float).powf(1.0 / 6.0);
+        let mut state = particles(&[0.0, 0.0, r_min, 0.0]);
+        let force = pair_potential(lj.clone(), 2.5, false, 2);
+        force.apply(&mut state);
+        assert!(state.accelerations.amax() < 1e-4);
+        assert!((force.energy(&state) + 2.0).abs() < 1e-5);
+
+        // Shifted energy is zero at the cutoff and the minimum is raised by the shift
+        let shifted = pair_potential(lj, 2.5, true, 2);
+        assert!((shifted.energy(&state) + 2.0 - shifted.energy_shift.abs()).abs() < 1e-5);
+        let state = particles(&[0.0, 0.0, 2.6, 0.0]);
+        assert_eq!(shifted.energy(&state), 0.0);
+    }
+
+    #[test]
+    fn morse_matches_analytical_derivative() {
+        let morse = Potential::Morse(Morse { depth: 1.5, width: 2.0, r0: 1.0 });
+        for r in [0.8, 1.0, 1.3, 2.0] {
+            let (energy, first, second) = morse.evaluate(r);
+            let h = 1e-3;
+            assert!((first - (morse.evaluate(r + h).0 - morse.evaluate(r - h).0) / (2.0 * h)).abs() < 1e-2);
+            assert!((second - (morse.evaluate(r + h).1 - morse.evaluate(r - h).1) / (2.0 * h)).abs() < 1e-1);
+            if r == 1.0 {
+                assert!((energy + 1.5).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn tabulated_potential_interpolates_smoothly() {
+        // Table of a harmonic potential U = (r - 1)^2
+        let r = (0..=20).map(|k| 0.5 + 0.05 * k as Float).collect::<Vec<_>>();
+        let energy = r.iter().map(|r| (r - 1.0) * (r - 1.0)).collect();
+        let table = TabulatedPotential::from_table(r, energy).unwrap();
+
+        // Values between the points are close to the function
+        for r in [0.63, 0.97, 1.21, 1.44] {
+            let (energy, first, _) = table.evaluate(r);
+            assert!((energy - (r - 1.0) * (r - 1.0)).abs() < 1e-4);
+            assert!((first - 2.0 * (r - 1.0)).abs() < 1e-2);
+        }
+
+        // Constant force with the slope of the first segment (-0.95) below the table
+        let (energy, first, second) = table.evaluate(0.3);
+        assert!((energy - 0.44).abs() < 1e-4 && (first + 0.95).abs() < 1e-4 && second == 0.0);
+
+        // Invalid tables are rejected
+        assert!(TabulatedPotential::from_table(vec![1.0, 0.5], vec![0.0, 0.0]).is_err());
+    }
+
+    #[test]
+    fn pair_potential_jacobian_matches_finite_differences() {
+        let force = pair_potential(Potential::LennardJones(LennardJones { epsilon: 1.0, sigma: 1.0 }), 2.5, true, 3);
+        let state = particles(&[0.0, 0.0, 1.1, 0.2, 0.4, 1.2]);
+
+        // Analytical jacobian
+        let mut da_dx = DMatrix::zeros(6, 6);
+        let mut da_dv = DMatrix::zeros(6, 6);
+        force.add_jacobian(&state, &mut da_dx, &mut da_dv);
+
+        // Finite difference jacobian
+        let mut scratch = state.clone();
+        let mut expected_da_dx = DMatrix::zeros(6, 6);
+        let mut expected_da_dv = DMatrix::zeros(6, 6);
+        finite_difference_jacobian(&mut |positions, velocities, accelerations| {
+            scratch.positions.copy_from(positions);
+            scratch.velocities.copy_from(velocities);
+            scratch.accelerations.fill(0.0);
+            force.apply(&mut scratch);
+            accelerations.copy_from(&scratch.accelerations);
+        }, &state.positions, &state.velocities, &mut expected_da_dx, &mut expected_da_dv);
+
+        // Entries are large close to the repulsive wall, so compare relative to them
+        assert!((&da_dx - &expected_da_dx).amax() < 1e-2 * expected_da_dx.amax());
+    }
+}
\ No newline at end of file
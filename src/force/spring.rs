@@ -1,92 +1,324 @@
+// External imports
+use nalgebra::{DMatrix, Matrix2, Vector2};
+
 // PyO3 imports
-use pyo3::prelude::*;
+use pyo3::{prelude::*, exceptions::PyValueError};
 
 // Crate imports
-use crate::{Float, scene::MassRef};
+use crate::{Float, Vec2, scene::{MassRef, BodyRef, Scene, State}};
 
-use super::ForceGenerator;
+use super::{ForceGenerator, NativeForce, add_pair_jacobian, scene_of};
 
 // Spring implementation
 #[pyclass(name="SpringForce", extends=super::ForceGenerator)]
 pub struct SpringForce {
-    // Spring constant
-    k: Float,
-    // Rest length
-    rest_length: Float,
+    // Native implementation
+    pub(crate) native: Spring,
 
-    // Masses
-    m1: Py<MassRef>,
-    m2: Py<MassRef>,
+    // Scene of the masses
+    pub(crate) scene: Option<Py<Scene>>,
 }
 
 // Spring implementation
 #[pymethods]
 impl SpringForce {
-    // Constructor
+    // Constructor (python keyword arguments)
     #[new]
-    fn new(m1: Py<MassRef>, m2: Py<MassRef>, k: Option<Float>, rest_length: Option<Float>, py: Python) -> (Self, ForceGenerator) {
+    #[allow(clippy::too_many_arguments)]
+    fn new(m1: Py<MassRef>, m2: Py<MassRef>, k: Option<Float>, rest_length: Option<Float>, damping: Option<Float>, offset1: Option<Vec2>, offset2: Option<Vec2>, py: Python) -> PyResult<(Self, ForceGenerator)> {
+        // Ends can be attached to bodies at offsets in their local frame
+        let end = |mass: &Py<MassRef>, offset: Option<Vec2>| -> PyResult<(Option<usize>, Vector2<Float>)> {
+            let body = mass.as_ref(py).downcast::<PyCell<BodyRef>>().ok().map(|body| body.borrow().body);
+            match (body, offset) {
+                (None, Some(_)) => Err(PyValueError::new_err("Spring can be attached at an offset only to a body")),
+                (body, offset) => Ok((body, offset.map_or(Vector2::zeros(), |offset| Vector2::new(offset.0, offset.1)))),
+            }
+        };
+        let (body1, offset1) = end(&m1, offset1)?;
+        let (body2, offset2) = end(&m2, offset2)?;
+
         // Create spring
-        (SpringForce {
+        let (scene, indices) = scene_of(py, &[m1, m2])?;
+        let mut spring = Spring {
             k: k.unwrap_or(1.0), // Default spring constant is 1
-            // Default rest length is distance between masses
-            rest_length: rest_length.unwrap_or_else(|| (m1.borrow_mut(py).raw_position(py) - m2.borrow_mut(py).raw_position(py)).norm()),
-            m1,
-            m2,
-        }, ForceGenerator::default())
+            rest_length: 0.0,
+            damping: damping.unwrap_or(0.0), // Purely elastic by default
+            m1: indices[0],
+            m2: indices[1],
+            body1,
+            body2,
+            offset1,
+            offset2,
+        };
+
+        // Default rest length is distance between ends
+        spring.rest_length = match (rest_length, &scene) {
+            (Some(rest_length), _) => rest_length,
+            (None, Some(scene)) => spring.ends(&scene.borrow(py).state).0.norm(),
+            (None, None) => 0.0,
+        };
+        Ok((SpringForce { native: spring, scene }, ForceGenerator))
     }
 
     // Get energy
     fn get_energy(&self, py: Python) -> PyResult<Float> {
-        // Get masses
-        let m1 = self.m1.borrow_mut(py);
-        let m2 = self.m2.borrow_mut(py);
+        Ok(self.scene.as_ref().map_or(0.0, |scene| self.native.energy(&scene.borrow(py).state)))
+    }
 
-        // Get positions
-        let p1 = m1.raw_position(py);
-        let p2 = m2.raw_position(py);
+    // Apply force
+    fn apply_force(&self, py: Python) -> PyResult<()> {
+        if let Some(scene) = &self.scene {
+            self.native.apply(&mut scene.borrow_mut(py).state);
+        }
 
-        // Get rest length
-        let rest_length = self.rest_length;
+        // Return Ok(()) to indicate that initialization was successful
+        Ok(())
+    }
 
-        // Get spring constant
-        let k = self.k;
+    // __doc__ attribute
+    fn __doc__(&self) -> &'static str {
+        "Spring force generator with optional damping (native implementation)"
+    }
+}
+
+// Native spring implementation
+#[derive(Clone)]
+pub(crate) struct Spring {
+    // Spring constant
+    k: Float,
+    // Rest length
+    rest_length: Float,
+    // Damping coefficient of the dashpot along the spring (Kelvin-Voigt model)
+    damping: Float,
 
-        // Calculate energy
-        let energy = 0.5 * k * (p1 - p2).norm_squared() - rest_length;
+    // Indices of masses
+    m1: usize,
+    m2: usize,
+
+    // Bodies the ends are attached to and offsets of the ends in their local frames
+    body1: Option<usize>,
+    body2: Option<usize>,
+    offset1: Vector2<Float>,
+    offset2: Vector2<Float>,
+}
 
-        // Return energy
-        Ok(energy)
+// Native spring implementation
+impl Spring {
+    // Get separation of the ends (p1 - p2), their relative velocity and offsets of the ends from the masses
+    fn ends(&self, state: &State) -> (Vector2<Float>, Vector2<Float>, Vector2<Float>, Vector2<Float>) {
+        let end = |mass: usize, body: Option<usize>, offset: Vector2<Float>| match body {
+            Some(body) => {
+                let offset = state.body_offset(body, offset);
+                (offset, state.body_point_velocity(body, offset))
+            },
+            None => (Vector2::zeros(), state.velocity(mass)),
+        };
+        let (r1, v1) = end(self.m1, self.body1, self.offset1);
+        let (r2, v2) = end(self.m2, self.body2, self.offset2);
+        (state.separation(self.m1, self.m2) + r1 - r2, v1 - v2, r1, r2)
     }
-    
-    // Apply force
-    fn apply_force(&self, py: Python) -> PyResult<()> {
-        // Get masses
-        let m1 = self.m1.borrow_mut(py);
-        let m2 = self.m2.borrow_mut(py);
 
-        // Get positions
-        let p1 = m1.raw_position(py);
-        let p2 = m2.raw_position(py);
+    // Apply force at an end (bodies also feel its torque)
+    fn apply_at(state: &mut State, mass: usize, body: Option<usize>, force: Vector2<Float>, offset: Vector2<Float>) {
+        match body {
+            Some(body) => state.apply_force_at(body, force, offset),
+            None => state.apply_force(mass, force),
+        }
+    }
+}
 
-        // Get rest length
-        let rest_length = self.rest_length;
+// Native spring implementation
+impl NativeForce for Spring {
+    fn apply(&self, state: &mut State) {
+        // Get separation of ends
+        let (d, w, r1, r2) = self.ends(state);
 
-        // Get spring constant
-        let k = self.k;
+        // Calculate force (direction is undefined when ends meet, zero length springs pull with zero force there)
+        let r = d.norm();
+        let u = if r > 0.0 { d / r } else { Vector2::zeros() };
+        let mut force = self.k * u * (r - self.rest_length);
 
-        // Calculate force
-        let force = k * (p1 - p2).normalize() * ((p1 - p2).norm() - rest_length);
+        // Damping along the spring, proportional to the rate of extension
+        if self.damping != 0.0 {
+            force += self.damping * u * u.dot(&w);
+        }
 
         // Apply force
-        m1.raw_apply_force(py, -force);
-        m2.raw_apply_force(py, force);
+        Self::apply_at(state, self.m1, self.body1, -force, r1);
+        Self::apply_at(state, self.m2, self.body2, force, r2);
+    }
 
-        // Return Ok(()) to indicate that initialization was successful
-        Ok(())
+    fn energy(&self, state: &State) -> Float {
+        // U = 1/2 * k * (|p1 - p2| - L)^2
+        0.5 * self.k * (self.ends(state).0.norm() - self.rest_length).powi(2)
     }
 
-    // __doc__ attribute
-    fn __doc__(&self) -> &'static str {
-        "Spring force generator (native implementation)"
+    fn dissipation(&self, state: &State) -> Float {
+        // P = c * (dr/dt)^2
+        let (d, w, _, _) = self.ends(state);
+        let r = d.norm();
+        if self.damping == 0.0 || r == 0.0 {
+            return 0.0;
+        }
+        self.damping * (d.dot(&w) / r).powi(2)
+    }
+
+    fn box_clone(&self) -> Box<dyn NativeForce> {
+        Box::new(self.clone())
+    }
+
+    // Only used without bodies (scene approximates jacobians of bodies with finite differences)
+    fn add_jacobian(&self, state: &State, da_dx: &mut DMatrix<Float>, da_dv: &mut DMatrix<Float>) {
+        // Get distance between masses
+        let d = state.separation(self.m1, self.m2);
+        let r = d.norm();
+        if r == 0.0 {
+            return;
+        }
+        let u = d / r;
+
+        // dF/dd = k * ((1 - L/r) * I + L/r * u * u^T)
+        let ratio = self.rest_length / r;
+        let stiffness = self.k * (Matrix2::identity() * (1.0 - ratio) + u * u.transpose() * ratio);
+
+        // Add jacobian
+        add_pair_jacobian(da_dx, state, self.m1, self.m2, stiffness);
+
+        // Damping force c * u * (u . w) with w = v1 - v2
+        if self.damping != 0.0 {
+            // dF/dw = c * u * u^T
+            add_pair_jacobian(da_dv, state, self.m1, self.m2, self.damping * u * u.transpose());
+
+            // dF/dd = c / r * ((u . w) * P + u * w^T * P) with P = I - u * u^T
+            let w = state.velocity(self.m1) - state.velocity(self.m2);
+            let projection = Matrix2::identity() - u * u.transpose();
+            let stiffness = self.damping / r * (projection * u.dot(&w) + u * w.transpose() * projection);
+            add_pair_jacobian(da_dx, state, self.m1, self.m2, stiffness);
+        }
+    }
+}
+
+// Test module
+#[cfg(test)]
+mod tests {
+    use nalgebra::DVector;
+
+    use super::*;
+    use crate::{solvers::{finite_difference_jacobian, ODESolver, VerletODE, Yoshida4}, test_utils::test_state};
+
+    // Spring between first two masses
+    fn spring(k: Float, rest_length: Float, damping: Float) -> Spring {
+        Spring { k, rest_length, damping, m1: 0, m2: 1, body1: None, body2: None, offset1: Vector2::zeros(), offset2: Vector2::zeros() }
+    }
+
+    // Total energy of two masses on a spring after each step of given solver
+    fn oscillator_energies(mut solver: impl ODESolver, steps: usize) -> Vec<Float> {
+        // Stretched spring between masses (period is about 2.6)
+        let mut state = test_state(&[1.0, 2.0], &[0.0, 0.0, 1.5, 0.0], &[0.0, 0.1, 0.0, -0.1]);
+        let spring = spring(4.0, 1.0, 0.0);
+
+        // Simulate
+        let mut energies = Vec::with_capacity(steps);
+        for _ in 0..steps {
+            let mut positions = state.positions.clone();
+            let mut velocities = state.velocities.clone();
+            let mut accelerations = DVector::zeros(4);
+            spring.apply(&mut state);
+            accelerations.copy_from(&state.accelerations);
+            solver.solve(0.01, &mut positions, &mut velocities, &mut accelerations, &mut |p, v, a| {
+                state.positions.copy_from(p);
+                state.velocities.copy_from(v);
+                state.accelerations.fill(0.0);
+                spring.apply(&mut state);
+                a.copy_from(&state.accelerations);
+            });
+            state.positions = positions;
+            state.velocities = velocities;
+            state.accelerations.fill(0.0);
+            energies.push(state.kinetic_energy() + spring.energy(&state));
+        }
+        energies
+    }
+
+    #[test]
+    fn spring_energy_is_hookean() {
+        let state = test_state(&[1.0, 1.0], &[0.0, 0.0, 3.0, 4.0], &[0.0; 4]);
+        let spring = spring(2.0, 3.0, 0.0);
+
+        // U = 1/2 * 2 * (5 - 3)^2
+        assert!((spring.energy(&state) - 4.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn spring_oscillator_conserves_energy_with_symplectic_solvers() {
+        // Around 40 periods
+        for energies in [oscillator_energies(VerletODE, 10000), oscillator_energies(Yoshida4, 10000)] {
+            let initial = energies[0];
+            let drift = energies.iter().map(|energy| (energy - initial).abs()).fold(0.0, Float::max);
+            assert!(drift < 1e-3 * initial, "energy drift {} of {}", drift, initial);
+        }
+    }
+
+    #[test]
+    fn damped_spring_jacobian_matches_finite_differences() {
+        // Stretched spring with masses moving apart and sideways
+        let mut state = test_state(&[1.0, 2.0], &[0.0, 0.0, 1.5, 0.5], &[-0.3, 0.2, 0.4, -0.6]);
+        let spring = spring(3.0, 1.0, 0.7);
+
+        // Analytical jacobians
+        let mut da_dx = DMatrix::zeros(4, 4);
+        let mut da_dv = DMatrix::zeros(4, 4);
+        spring.add_jacobian(&state, &mut da_dx, &mut da_dv);
+
+        // Finite differences
+        let mut fd_da_dx = DMatrix::zeros(4, 4);
+        let mut fd_da_dv = DMatrix::zeros(4, 4);
+        let (positions, velocities) = (state.positions.clone(), state.velocities.clone());
+        finite_difference_jacobian(&mut |p, v, a| {
+            state.positions.copy_from(p);
+            state.velocities.copy_from(v);
+            state.accelerations.fill(0.0);
+            spring.apply(&mut state);
+            a.copy_from(&state.accelerations);
+        }, &positions, &velocities, &mut fd_da_dx, &mut fd_da_dv);
+
+        // Compare
+        assert!((da_dx - fd_da_dx).amax() < 1e-2);
+        assert!((da_dv - fd_da_dv).amax() < 1e-2);
+    }
+
+    #[test]
+    fn spring_on_body_applies_torque_of_its_energy() {
+        // Rotated body at the origin and a fixed mass
+        let mut state = State {
+            inverse_masses: DVector::from_vec(vec![0.5, 0.0]),
+            bodies: vec![0],
+            angles: DVector::from_vec(vec![0.3]),
+            angular_velocities: DVector::zeros(1),
+            angular_accelerations: DVector::zeros(1),
+            inertias: DVector::from_vec(vec![0.25]),
+            inverse_inertias: DVector::from_vec(vec![4.0]),
+            ..test_state(&[2.0, 1.0], &[0.0, 0.0, 2.0, 1.0], &[0.0; 4])
+        };
+        let spring = Spring { body1: Some(0), offset1: Vector2::new(1.0, 0.5), ..spring(3.0, 0.5, 0.0) };
+        spring.apply(&mut state);
+
+        // F = -dU/dx and torque = -dU/dangle
+        let eps = 1e-3;
+        let derivative = |state: &mut State, coordinate: &mut dyn FnMut(&mut State) -> &mut Float| {
+            *coordinate(state) += eps;
+            let forward = spring.energy(state);
+            *coordinate(state) -= 2.0 * eps;
+            let backward = spring.energy(state);
+            *coordinate(state) += eps;
+            (forward - backward) / (2.0 * eps)
+        };
+        let force_x = -derivative(&mut state, &mut |state| &mut state.positions[0]);
+        let force_y = -derivative(&mut state, &mut |state| &mut state.positions[1]);
+        let torque = -derivative(&mut state, &mut |state| &mut state.angles[0]);
+        assert!((state.accelerations[0] * 2.0 - force_x).abs() < 1e-2);
+        assert!((state.accelerations[1] * 2.0 - force_y).abs() < 1e-2);
+        assert!((state.angular_accelerations[0] * 0.25 - torque).abs() < 1e-2);
+        assert!(torque.abs() > 0.1);
     }
 }
\ No newline at end of file
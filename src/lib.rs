@@ -1,8 +1,10 @@
 // Benchmark enable (nightly only)
 #![cfg_attr(feature="bench", feature(test))]
+// PyO3 generates fallible conversions for Py<Self> receivers and nested impls for __setitem__
+#![allow(clippy::unnecessary_fallible_conversions, non_local_definitions)]
 
 // PyO3 imports
-use pyo3::{prelude::*, wrap_pymodule};
+use pyo3::{prelude::*, wrap_pymodule, wrap_pyfunction};
 
 // Base float precision
 #[cfg(feature = "f64precision")]
@@ -17,7 +19,11 @@ pub type Vec2 = (Float, Float);
 mod scene;
 mod solvers;
 mod force;
+mod constraint;
+mod collision;
+mod collider;
 mod data_collector;
+mod batch;
 
 // Base module
 #[pymodule]
@@ -31,6 +37,9 @@ fn pyphyrs(_py: Python, m: &PyModule) -> PyResult<()> {
     // Add scene class
     m.add_class::<scene::Scene>()?;
 
+    // Add parallel simulation of many scenes
+    m.add_function(wrap_pyfunction!(batch::batch_simulate, m)?)?;
+
     // ====< Submodules >====
     // Add solvers submodule
     m.add_wrapped(wrap_pymodule!(solvers::solvers))?;
@@ -38,6 +47,62 @@ fn pyphyrs(_py: Python, m: &PyModule) -> PyResult<()> {
     // Add force submodule
     m.add_wrapped(wrap_pymodule!(force::force))?;
 
+    // Add constraint submodule
+    m.add_wrapped(wrap_pymodule!(constraint::constraint))?;
+
+    // Add collider submodule
+    m.add_wrapped(wrap_pymodule!(collider::collider))?;
+
     // Return Ok(()) to indicate that initialization was successful
     Ok(())
+}
+
+// Test utilities
+#[cfg(test)]
+pub(crate) mod test_utils {
+    // External imports
+    use nalgebra::DVector;
+
+    // PyO3 imports
+    use pyo3::{prelude::*, types::PyDict, once_cell::GILOnceCell};
+
+    // Crate imports
+    use crate::{Float, scene::State};
+
+    // Library module (python modules can be initialized only once per process)
+    static MODULE: GILOnceCell<Py<PyModule>> = GILOnceCell::new();
+
+    // Run given closure with the GIL (starts python if it's not running yet)
+    pub(crate) fn with_python<R>(f: impl FnOnce(Python) -> R) -> R {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(f)
+    }
+
+    // Run python code with the library available as pyphyrs, returns its globals
+    pub(crate) fn run_python<'py>(py: Python<'py>, code: &str) -> &'py PyDict {
+        let module = MODULE.get_or_init(py, || {
+            let module = PyModule::new(py, "pyphyrs").unwrap();
+            super::pyphyrs(py, module).unwrap();
+            module.into()
+        });
+        let globals = PyDict::new(py);
+        globals.set_item("pyphyrs", module).unwrap();
+        py.run(code, Some(globals), None).unwrap();
+        globals
+    }
+
+    // State of uncharged masses with given masses (zero for pinned ones), positions and velocities
+    pub(crate) fn test_state(masses: &[Float], positions: &[Float], velocities: &[Float]) -> State {
+        let n = masses.len();
+        State {
+            positions: DVector::from_row_slice(positions),
+            velocities: DVector::from_row_slice(velocities),
+            accelerations: DVector::zeros(n * 2),
+            masses: DVector::from_row_slice(masses),
+            inverse_masses: DVector::from_iterator(n, masses.iter().map(|m| if *m == 0.0 { 0.0 } else { 1.0 / m })),
+            charges: DVector::zeros(n),
+            magnetic_fields: DVector::zeros(n),
+            ..Default::default()
+        }
+    }
 }
\ No newline at end of file
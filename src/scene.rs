@@ -1,11 +1,288 @@
+// Standard imports
+use std::{cell::RefCell, sync::Arc};
+
 // External imports
-use nalgebra::{Vector2, DVector};
+use nalgebra::{Vector2, DVector, DMatrix};
 
 // PyO3 imports
-use pyo3::{prelude::*, intern};
+use pyo3::{prelude::*, intern, exceptions::PyValueError};
 
 // Crate imports
-use crate::{Float, Vec2, solvers::{ODESolver, EulerODE}, data_collector::InMemoryDataCollector};
+use crate::{Float, Vec2, force::{self, NativeForce}, constraint::{self, Constraint, ConstraintReport}, collision::Collisions, collider, solvers::{self, ODESolver, EulerODE, finite_difference_jacobian}, data_collector::InMemoryDataCollector};
+
+// Function giving position of a kinematic mass at given time
+pub(crate) type TrajectoryFn = dyn Fn(Float) -> Vector2<Float> + Send + Sync;
+
+// Wrap python function of time returning position of a kinematic mass
+fn python_trajectory(function: PyObject) -> Arc<TrajectoryFn> {
+    Arc::new(move |time| {
+        Python::with_gil(|py| {
+            let position: Vec2 = function.call1(py, (time,)).and_then(|position| position.extract(py))
+                .expect("Error while evaluating trajectory");
+            Vector2::new(position.0, position.1)
+        })
+    })
+}
+
+// Simulation state, stored as vectors where each object takes two entries (x, y)
+#[derive(Clone)]
+pub(crate) struct State {
+    pub(crate) positions: DVector<Float>,
+    pub(crate) velocities: DVector<Float>,
+    pub(crate) accelerations: DVector<Float>,
+    pub(crate) masses: DVector<Float>,
+    pub(crate) charges: DVector<Float>,
+
+    // Inverse masses (zero for pinned and zero mass objects, which forces can't move)
+    pub(crate) inverse_masses: DVector<Float>,
+    // Whether masses are pinned (stay in place or follow a trajectory)
+    pub(crate) pinned: Vec<bool>,
+    // Radii of masses (zero for points, which don't collide)
+    pub(crate) radii: DVector<Float>,
+
+    // Rigid bodies, each one is a mass (at its centre of mass) with an orientation
+    // Indices of masses of bodies
+    pub(crate) bodies: Vec<usize>,
+    pub(crate) angles: DVector<Float>,
+    pub(crate) angular_velocities: DVector<Float>,
+    pub(crate) angular_accelerations: DVector<Float>,
+    pub(crate) inertias: DVector<Float>,
+    // Inverse moments of inertia (zero for bodies which can't rotate)
+    pub(crate) inverse_inertias: DVector<Float>,
+
+    // Magnetic field (perpendicular to the plane) at each mass, filled by field forces
+    pub(crate) magnetic_fields: DVector<Float>,
+
+    // Total energy dissipated by damping so far
+    pub(crate) dissipated_energy: Float,
+
+    // Time since the scene was created (at the start of the current substep)
+    pub(crate) time: Float,
+
+    // Box the scene is simulated in
+    pub(crate) domain: Option<Domain>,
+    // Distance each mass was moved by wrapping into a periodic domain (unwrapped positions are positions + offsets)
+    pub(crate) unwrap_offsets: DVector<Float>,
+}
+
+// Rectangular domain of the scene
+#[derive(Clone, Copy)]
+pub(crate) struct Domain {
+    pub(crate) min: Vector2<Float>,
+    pub(crate) max: Vector2<Float>,
+    // Masses leaving the domain come back on the opposite side and forces act through the boundaries
+    pub(crate) periodic: bool,
+}
+
+// Domain implementation
+impl Domain {
+    // Get size of the domain
+    pub(crate) fn size(&self) -> Vector2<Float> {
+        self.max - self.min
+    }
+}
+
+// Default implementation
+impl Default for State {
+    fn default() -> Self {
+        // Create empty state
+        State {
+            positions: DVector::zeros(0),
+            velocities: DVector::zeros(0),
+            accelerations: DVector::zeros(0),
+            masses: DVector::zeros(0),
+            charges: DVector::zeros(0),
+            inverse_masses: DVector::zeros(0),
+            pinned: Vec::new(),
+            radii: DVector::zeros(0),
+            bodies: Vec::new(),
+            angles: DVector::zeros(0),
+            angular_velocities: DVector::zeros(0),
+            angular_accelerations: DVector::zeros(0),
+            inertias: DVector::zeros(0),
+            inverse_inertias: DVector::zeros(0),
+            magnetic_fields: DVector::zeros(0),
+            dissipated_energy: 0.0,
+            time: 0.0,
+            domain: None,
+            unwrap_offsets: DVector::zeros(0),
+        }
+    }
+}
+
+// State implementation
+impl State {
+    // Get position of a mass
+    pub(crate) fn position(&self, index: usize) -> Vector2<Float> {
+        Vector2::new(self.positions[index * 2], self.positions[index * 2 + 1])
+    }
+
+    // Get velocity of a mass
+    pub(crate) fn velocity(&self, index: usize) -> Vector2<Float> {
+        Vector2::new(self.velocities[index * 2], self.velocities[index * 2 + 1])
+    }
+
+    // Recalculate inverse mass of a mass after changing its mass or pinning it
+    pub(crate) fn update_inverse_mass(&mut self, index: usize) {
+        self.inverse_masses[index] = if self.pinned[index] || self.masses[index] == 0.0 { 0.0 } else { 1.0 / self.masses[index] };
+    }
+
+    // Set position of a mass (its unwrapped position starts over)
+    pub(crate) fn set_position(&mut self, index: usize, position: Vector2<Float>) {
+        self.positions[index * 2] = position.x;
+        self.positions[index * 2 + 1] = position.y;
+        self.unwrap_offsets[index * 2] = 0.0;
+        self.unwrap_offsets[index * 2 + 1] = 0.0;
+    }
+
+    // Get periodic domain of the state (None if it isn't periodic)
+    pub(crate) fn periodic_domain(&self) -> Option<&Domain> {
+        self.domain.as_ref().filter(|domain| domain.periodic)
+    }
+
+    // Get nearest periodic image of a separation vector
+    pub(crate) fn minimum_image(&self, d: Vector2<Float>) -> Vector2<Float> {
+        match self.periodic_domain() {
+            Some(domain) => {
+                let size = domain.size();
+                Vector2::new(d.x - size.x * (d.x / size.x).round(), d.y - size.y * (d.y / size.y).round())
+            },
+            None => d,
+        }
+    }
+
+    // Get separation of two masses (p1 - p2), through the boundaries of a periodic domain
+    pub(crate) fn separation(&self, i1: usize, i2: usize) -> Vector2<Float> {
+        self.minimum_image(self.position(i1) - self.position(i2))
+    }
+
+    // Wrap positions into a periodic domain (returns whether any mass was moved)
+    pub(crate) fn wrap_positions(&mut self) -> bool {
+        let Some(domain) = self.periodic_domain().copied() else {
+            return false;
+        };
+        let size = domain.size();
+        let mut moved = false;
+        for i in 0..self.positions.len() {
+            let (min, size) = if i % 2 == 0 { (domain.min.x, size.x) } else { (domain.min.y, size.y) };
+            let wrapped = min + (self.positions[i] - min).rem_euclid(size);
+            if wrapped != self.positions[i] {
+                self.unwrap_offsets[i] += self.positions[i] - wrapped;
+                self.positions[i] = wrapped;
+                moved = true;
+            }
+        }
+        moved
+    }
+
+    // Get positions as if masses were never wrapped into a periodic domain
+    pub(crate) fn unwrapped_positions(&self) -> DVector<Float> {
+        &self.positions + &self.unwrap_offsets
+    }
+
+    // Get magnetic gyration frequencies (q * B / m) of all masses
+    pub(crate) fn gyrofrequencies(&self) -> DVector<Float> {
+        DVector::from_fn(self.masses.len(), |i, _| self.charges[i] * self.magnetic_fields[i] * self.inverse_masses[i])
+    }
+
+    // Get kinetic energy of all masses
+    pub(crate) fn kinetic_energy(&self) -> Float {
+        let mut energy = 0.0;
+        for i in 0..self.masses.len() {
+            if self.masses[i] == 0.0 {
+                continue;
+            }
+
+            // Ek = 1/2 * m * v^2
+            energy += 0.5 * self.masses[i] * (self.velocities[i * 2].powi(2) + self.velocities[i * 2 + 1].powi(2));
+        }
+
+        // Ek = 1/2 * I * w^2
+        energy + 0.5 * self.inertias.dot(&self.angular_velocities.component_mul(&self.angular_velocities))
+    }
+
+    // Get positions and velocities of all degrees of freedom integrated by the solver
+    // (angles of bodies follow positions of masses)
+    pub(crate) fn coordinates(&self) -> (DVector<Float>, DVector<Float>) {
+        if self.bodies.is_empty() {
+            return (self.positions.clone(), self.velocities.clone());
+        }
+        let n = self.positions.len() + self.angles.len();
+        (
+            DVector::from_iterator(n, self.positions.iter().chain(self.angles.iter()).copied()),
+            DVector::from_iterator(n, self.velocities.iter().chain(self.angular_velocities.iter()).copied()),
+        )
+    }
+
+    // Move state into given positions and velocities of all degrees of freedom
+    pub(crate) fn set_coordinates(&mut self, positions: &DVector<Float>, velocities: &DVector<Float>) {
+        let (n, k) = (self.positions.len(), self.angles.len());
+        self.positions.copy_from(&positions.rows(0, n));
+        self.velocities.copy_from(&velocities.rows(0, n));
+        self.angles.copy_from(&positions.rows(n, k));
+        self.angular_velocities.copy_from(&velocities.rows(n, k));
+    }
+
+    // Get accelerations of all degrees of freedom
+    pub(crate) fn read_accelerations(&self, accelerations: &mut DVector<Float>) {
+        let (n, k) = (self.accelerations.len(), self.angular_accelerations.len());
+        accelerations.rows_mut(0, n).copy_from(&self.accelerations);
+        accelerations.rows_mut(n, k).copy_from(&self.angular_accelerations);
+    }
+
+    // Reset accelerations of all degrees of freedom
+    pub(crate) fn clear_accelerations(&mut self) {
+        self.accelerations.fill(0.0);
+        self.angular_accelerations.fill(0.0);
+    }
+
+    // Get offset of a point fixed to a body (given in its local frame) from the centre of the body
+    pub(crate) fn body_offset(&self, body: usize, local: Vector2<Float>) -> Vector2<Float> {
+        let (sin, cos) = self.angles[body].sin_cos();
+        Vector2::new(cos * local.x - sin * local.y, sin * local.x + cos * local.y)
+    }
+
+    // Get velocity of a point at given offset from the centre of a body
+    pub(crate) fn body_point_velocity(&self, body: usize, offset: Vector2<Float>) -> Vector2<Float> {
+        // v + w x r
+        self.velocity(self.bodies[body]) + Vector2::new(-offset.y, offset.x) * self.angular_velocities[body]
+    }
+
+    // Apply force at given offset from the centre of a body
+    pub(crate) fn apply_force_at(&mut self, body: usize, force: Vector2<Float>, offset: Vector2<Float>) {
+        self.apply_force(self.bodies[body], force);
+        self.apply_torque(body, offset.x * force.y - offset.y * force.x);
+    }
+
+    // Apply torque to a body
+    pub(crate) fn apply_torque(&mut self, body: usize, torque: Float) {
+        if torque.is_nan() {
+            panic!("Torque is NaN");
+        }
+        self.angular_accelerations[body] += torque * self.inverse_inertias[body];
+    }
+
+    // Recalculate inverse moment of inertia of a body after changing it
+    pub(crate) fn update_inverse_inertia(&mut self, body: usize) {
+        self.inverse_inertias[body] = if self.inertias[body] == 0.0 { 0.0 } else { 1.0 / self.inertias[body] };
+    }
+
+    // Apply force to a mass
+    pub(crate) fn apply_force(&mut self, index: usize, force: Vector2<Float>) {
+        // Check if force is NaN
+        if force.x.is_nan() || force.y.is_nan() {
+            panic!("Force is NaN");
+        }
+
+        // Apply force (pinned masses don't feel it)
+        let inverse_mass = self.inverse_masses[index];
+        if inverse_mass == 0.0 {
+            return;
+        }
+        self.accelerations[index * 2] += force.x * inverse_mass;
+        self.accelerations[index * 2 + 1] += force.y * inverse_mass;
+    }
+}
 
 // Scene class definition
 #[pyclass]
@@ -13,18 +290,82 @@ pub struct Scene {
     // Properties
     gravity: Vector2<Float>,
 
-    // Objects stored as a vector where each object is a vector of its positions
-    pub(crate) positions: DVector<Float>,
-    pub(crate) velocities: DVector<Float>,
-    pub(crate) accelerations: DVector<Float>,
-    pub(crate) masses: DVector<Float>,
+    // Objects
+    pub(crate) state: State,
 
     // Technicals
     ode_solver: Box<dyn Send + ODESolver>,
+    // Accelerations the solver left for the current state (reused by the next substep if nothing moved the state since)
+    accelerations: Option<DVector<Float>>,
     pub(crate) data_collector: InMemoryDataCollector,
 
     // Other things
+    native_forces: Vec<Box<dyn NativeForce>>,
     force_generators: Vec<PyObject>,
+
+    // Order in which force generators were added
+    force_order: Vec<ForceSlot>,
+
+    // Constraints enforced after each step
+    constraints: Vec<Box<dyn Constraint>>,
+    // Impulses of constraints since the last collected frame
+    constraint_impulses: Vec<Float>,
+    // Maximum number of iterations of constraint projection
+    constraint_iterations: usize,
+
+    // Trajectories of kinematic masses (with their indices)
+    trajectories: Vec<(usize, Arc<TrajectoryFn>)>,
+
+    // Collisions of masses with a radius
+    collisions: Collisions,
+}
+
+// Position of a force generator in native or python force generators
+#[derive(Clone, Copy)]
+enum ForceSlot {
+    Native(usize),
+    Python(usize),
+}
+
+// Energies of the scene
+#[derive(Clone, Default)]
+pub(crate) struct Energies {
+    pub(crate) kinetic: Float,
+    // Potential energy of the scene gravity
+    pub(crate) gravity: Float,
+    // Potential energy of each force generator (in the order they were added)
+    pub(crate) potentials: Vec<Float>,
+    // Energy dissipated so far (not part of the total)
+    pub(crate) dissipated: Float,
+}
+
+// Energies implementation
+impl Energies {
+    // Get total energy
+    pub(crate) fn total(&self) -> Float {
+        self.kinetic + self.gravity + self.potentials.iter().sum::<Float>()
+    }
+
+    // Add energies of another state (with the same force generators)
+    fn add(&mut self, other: &Energies) {
+        self.kinetic += other.kinetic;
+        self.gravity += other.gravity;
+        self.dissipated += other.dissipated;
+        self.potentials.resize(other.potentials.len(), 0.0);
+        for (potential, other) in self.potentials.iter_mut().zip(other.potentials.iter()) {
+            *potential += other;
+        }
+    }
+
+    // Multiply all energies by a factor
+    fn scale(&mut self, factor: Float) {
+        self.kinetic *= factor;
+        self.gravity *= factor;
+        self.dissipated *= factor;
+        for potential in self.potentials.iter_mut() {
+            *potential *= factor;
+        }
+    }
 }
 
 // Default implementation
@@ -34,15 +375,23 @@ impl Default for Scene {
         Scene {
             gravity: Vector2::new(0.0, -9.81),
 
-            positions: DVector::zeros(0),
-            velocities: DVector::zeros(0),
-            accelerations: DVector::zeros(0),
-            masses: DVector::zeros(0),
+            state: State::default(),
 
             ode_solver: Box::new(EulerODE),
+            accelerations: None,
             data_collector: InMemoryDataCollector::new(),
 
+            native_forces: Vec::new(),
             force_generators: Vec::new(),
+            force_order: Vec::new(),
+
+            constraints: Vec::new(),
+            constraint_impulses: Vec::new(),
+            constraint_iterations: 20,
+
+            trajectories: Vec::new(),
+
+            collisions: Collisions::default(),
         }
     }
 }
@@ -53,36 +402,44 @@ impl Scene {
     // Constructor
     // TODO: Create signature for constructor
     #[new]
-    fn new(gravity: Option<Vec2>, ode: Option<&str>) -> Self {
+    fn new(gravity: Option<Vec2>, ode: Option<&PyAny>, domain: Option<(Vec2, Vec2)>, periodic: Option<bool>, unwrapped: Option<bool>) -> PyResult<Self> {
+        // Check domain
+        let domain = domain.map(|(min, max)| Domain {
+            min: Vector2::new(min.0, min.1),
+            max: Vector2::new(max.0, max.1),
+            periodic: periodic.unwrap_or(false),
+        });
+        if let Some(domain) = &domain {
+            if !(domain.min.x < domain.max.x && domain.min.y < domain.max.y) {
+                return Err(PyValueError::new_err("Domain has to be given as ((xmin, ymin), (xmax, ymax)) with min smaller than max"));
+            }
+        } else if periodic.unwrap_or(false) {
+            return Err(PyValueError::new_err("Periodic scene needs a domain"));
+        }
+
         // Create scene with default values
-        Self {
+        Ok(Self {
             gravity: match gravity {
                 Some(gravity) => Vector2::new(gravity.0, gravity.1),
                 None => Vector2::new(0.0, -9.81)
             },
             ode_solver: match ode {
-                Some(ode) => {
-                    match ode {
-                        "euler" => Box::new(EulerODE),
-                        _ => panic!("Unknown ODE solver: {}", ode)
-                    }
-                },
+                Some(ode) => solvers::solver_from_py(ode)?,
                 None => Box::new(EulerODE)
             },
+            state: State {
+                domain,
+                ..Default::default()
+            },
+            data_collector: InMemoryDataCollector::with_unwrapped(unwrapped.unwrap_or(false)),
             ..Default::default()
-        }
+        })
     }
 
     // Add mass to the scene
     fn mass(mut self_: PyRefMut<Self>) -> PyResult<MassRef> {
-        // Get index of the mass
-        let index = self_.positions.len() / 2;
-
         // Add mass to the scene
-        self_.positions.extend(vec![0.0, 0.0]);
-        self_.velocities.extend(vec![0.0, 0.0]);
-        self_.accelerations.extend(vec![0.0, 0.0]);
-        self_.masses.extend([1.0]);
+        let index = self_.add_mass();
 
         // Return reference to the mass
         Ok(MassRef {
@@ -91,10 +448,33 @@ impl Scene {
         })
     }
 
+    // Add rigid body to the scene (mass at its centre with an orientation)
+    fn body(mut self_: PyRefMut<Self>, py: Python) -> PyResult<Py<BodyRef>> {
+        // Add mass of the body
+        let index = self_.add_mass();
+
+        // Add orientation of the body (with unit moment of inertia)
+        let body = self_.state.bodies.len();
+        self_.state.bodies.push(index);
+        self_.state.angles.extend([0.0]);
+        self_.state.angular_velocities.extend([0.0]);
+        self_.state.angular_accelerations.extend([0.0]);
+        self_.state.inertias.extend([1.0]);
+        self_.state.inverse_inertias.extend([1.0]);
+
+        // Return reference to the body
+        let mass = MassRef {
+            scene: self_.into(),
+            index
+        };
+        Py::new(py, PyClassInitializer::from(mass).add_subclass(BodyRef { body }))
+    }
+
     // Set gravity (as setter)
     #[setter(gravity)]
     fn set_gravity(&mut self, gravity: Vec2) {
         self.gravity = Vector2::new(gravity.0, gravity.1);
+        self.reset_solver();
     }
 
     // Get gravity (as getter)
@@ -103,9 +483,112 @@ impl Scene {
         (self.gravity.x, self.gravity.y)
     }
 
-    // Add force generator
-    fn add_force(&mut self, force_generator: PyObject) {
-        self.force_generators.push(force_generator);
+    // Get domain (as getter)
+    #[getter(domain)]
+    fn get_domain(&self) -> Option<((Float, Float), (Float, Float))> {
+        self.state.domain.map(|domain| ((domain.min.x, domain.min.y), (domain.max.x, domain.max.y)))
+    }
+
+    // Get whether the domain is periodic (as getter)
+    #[getter(periodic)]
+    fn get_periodic(&self) -> bool {
+        self.state.periodic_domain().is_some()
+    }
+
+    // Set ODE solver (as setter)
+    #[setter(ode)]
+    fn set_ode(&mut self, ode: &PyAny) -> PyResult<()> {
+        self.ode_solver = solvers::solver_from_py(ode)?;
+        self.accelerations = None;
+        Ok(())
+    }
+
+    // Get accepted and rejected step counts of adaptive solver (as getter)
+    #[getter(solver_stats)]
+    fn get_solver_stats(&self) -> Option<(usize, usize)> {
+        self.ode_solver.stats()
+    }
+
+    // Add force generator (native generators acting on this scene bypass python)
+    fn add_force(self_: Py<Self>, force_generator: PyObject, py: Python) {
+        let native_force = force::native_force(py, &force_generator, &self_);
+        let mut self_mut = self_.borrow_mut(py);
+        let slot = match native_force {
+            Some(native_force) => {
+                self_mut.native_forces.push(native_force);
+                ForceSlot::Native(self_mut.native_forces.len() - 1)
+            },
+            None => {
+                self_mut.force_generators.push(force_generator);
+                ForceSlot::Python(self_mut.force_generators.len() - 1)
+            },
+        };
+        self_mut.force_order.push(slot);
+        self_mut.reset_solver();
+    }
+
+    // Add constraint enforced after each step of the solver
+    fn add_constraint(self_: Py<Self>, constraint: &PyAny, py: Python) -> PyResult<()> {
+        let constraint = constraint::native_constraint(constraint, &self_)?;
+        let mut self_mut = self_.borrow_mut(py);
+        self_mut.constraints.push(constraint);
+        self_mut.constraint_impulses.push(0.0);
+        self_mut.reset_solver();
+        Ok(())
+    }
+
+    // Add static collider masses bounce off
+    fn add_collider(&mut self, collider: &PyAny) -> PyResult<()> {
+        self.collisions.colliders.push(collider::native_collider(collider)?);
+        Ok(())
+    }
+
+    // Set maximum number of iterations of constraint projection (as setter)
+    #[setter(constraint_iterations)]
+    fn set_constraint_iterations(&mut self, iterations: usize) -> PyResult<()> {
+        if iterations == 0 {
+            return Err(PyValueError::new_err("Constraints need at least one iteration"));
+        }
+        self.constraint_iterations = iterations;
+        Ok(())
+    }
+
+    // Get maximum number of iterations of constraint projection (as getter)
+    #[getter(constraint_iterations)]
+    fn get_constraint_iterations(&self) -> usize {
+        self.constraint_iterations
+    }
+
+    // Set coefficient of restitution of collisions (as setter)
+    #[setter(restitution)]
+    fn set_restitution(&mut self, restitution: Float) -> PyResult<()> {
+        if !(0.0..=1.0).contains(&restitution) {
+            return Err(PyValueError::new_err("Coefficient of restitution must be between 0 and 1"));
+        }
+        self.collisions.restitution = restitution;
+        Ok(())
+    }
+
+    // Get coefficient of restitution of collisions (as getter)
+    #[getter(restitution)]
+    fn get_restitution(&self) -> Float {
+        self.collisions.restitution
+    }
+
+    // Set coefficient of friction of collisions (as setter)
+    #[setter(friction)]
+    fn set_friction(&mut self, friction: Float) -> PyResult<()> {
+        if !(friction >= 0.0 && friction.is_finite()) {
+            return Err(PyValueError::new_err("Coefficient of friction must be finite and non-negative"));
+        }
+        self.collisions.friction = friction;
+        Ok(())
+    }
+
+    // Get coefficient of friction of collisions (as getter)
+    #[getter(friction)]
+    fn get_friction(&self) -> Float {
+        self.collisions.friction
     }
 
     // Simulate scene
@@ -116,14 +599,8 @@ impl Scene {
         // Time simulation
         let start = std::time::Instant::now();
 
-        let mut time = 0.0;
         // Simulate scene
-        for _ in 0..steps {
-            let energy = Self::update(&self_, dt, substeps, py);
-            data_collector.collect_frame(py, &self_, time, energy / substeps as Float);
-            time += dt;
-        }
-
+        Self::simulate_frames(&self_, &mut data_collector, steps, substeps, dt, py);
 
         println!("Simulation took {}ms", start.elapsed().as_millis());
         println!("Each step took {}ms", start.elapsed().as_millis() / steps as u128);
@@ -142,8 +619,7 @@ impl Scene {
         let start = std::time::Instant::now();
 
         // Simulate scene
-        let energy = Self::update(&self_, dt, substeps, py);
-        data_collector.collect_frame(py, &self_, 0.0, energy / substeps as Float);
+        Self::simulate_frames(&self_, &mut data_collector, 1, substeps, dt, py);
 
         #[cfg(feature="timings")]
         {
@@ -158,11 +634,11 @@ impl Scene {
     // Get positions
     fn positions(self_: PyRef<Self>) -> Vec<(Float, Float)> {
         // Create vector of positions
-        let mut positions = Vec::with_capacity(self_.positions.len() / 2);
+        let mut positions = Vec::with_capacity(self_.state.positions.len() / 2);
 
         // Fill vector
-        for i in 0..self_.positions.len() / 2 {
-            positions.push((self_.positions[i * 2], self_.positions[i * 2 + 1]));
+        for i in 0..self_.state.positions.len() / 2 {
+            positions.push((self_.state.positions[i * 2], self_.state.positions[i * 2 + 1]));
         }
 
         // Return positions
@@ -172,94 +648,466 @@ impl Scene {
 
 // Scene internal implementation
 impl Scene {
-    // Update scene
-    pub fn update(self_: &Py<Self>, dt: Float, substeps: usize, py: Python) -> Float {
+    // Add mass to the state, returns its index
+    fn add_mass(&mut self) -> usize {
+        // Get index of the mass
+        let index = self.state.positions.len() / 2;
+
+        // Add mass to the scene
+        self.state.positions.extend(vec![0.0, 0.0]);
+        self.state.velocities.extend(vec![0.0, 0.0]);
+        self.state.accelerations.extend(vec![0.0, 0.0]);
+        self.state.masses.extend([1.0]);
+        self.state.charges.extend([0.0]);
+        self.state.inverse_masses.extend([1.0]);
+        self.state.pinned.push(false);
+        self.state.radii.extend([0.0]);
+        self.state.magnetic_fields.extend([0.0]);
+        self.state.unwrap_offsets.extend(vec![0.0, 0.0]);
+
+        // Solver history is no longer valid
+        self.reset_solver();
+        index
+    }
+
+    // Simulate given number of frames and collect them
+    fn simulate_frames(self_: &Py<Self>, data_collector: &mut InMemoryDataCollector, steps: usize, substeps: usize, dt: Float, py: Python) {
+        // Python force generators could have been changed since the last simulation
+        let mut self_mut = self_.borrow_mut(py);
+        self_mut.accelerations = None;
+
+        // Scenes with only native force generators are simulated without the GIL,
+        // the scene stays borrowed so python threads can't modify it in the meantime
+        if self_mut.force_generators.is_empty() {
+            let scene = &mut *self_mut;
+            py.allow_threads(|| scene.simulate_native(data_collector, steps, substeps, dt));
+            return;
+        }
+        drop(self_mut);
+
+        // Python force generators need the GIL
+        let mut time = 0.0;
+        for _ in 0..steps {
+            let energies = Self::update(self_, dt, substeps, py);
+            self_.borrow_mut(py).collect_frame(data_collector, time, &energies, dt);
+            time += dt;
+        }
+    }
+
+    // Forget solver history and accelerations left by the solver (state was changed from outside)
+    fn reset_solver(&mut self) {
+        self.ode_solver.reset();
+        self.accelerations = None;
+    }
+
+    // Simulate given number of frames with only native force generators (doesn't need python)
+    pub(crate) fn simulate_native(&mut self, data_collector: &mut InMemoryDataCollector, steps: usize, substeps: usize, dt: Float) {
+        let mut time = 0.0;
+        for _ in 0..steps {
+            let energies = self.update_native(dt, substeps);
+            self.collect_frame(data_collector, time, &energies, dt);
+            time += dt;
+        }
+    }
+
+    // Collect current state with energies, constraints and collisions of a frame of length dt (and start a new frame)
+    fn collect_frame(&mut self, data_collector: &mut InMemoryDataCollector, time: Float, energies: &Energies, dt: Float) {
+        let constraints = self.constraint_report(dt);
+        let collisions = std::mem::take(&mut self.collisions.events);
+        data_collector.collect_frame(&self.state, time, energies, &constraints, &collisions);
+    }
+
+    // Get violations and reaction forces of constraints in a frame of length dt (and start a new frame)
+    fn constraint_report(&mut self, dt: Float) -> ConstraintReport {
+        let report = ConstraintReport {
+            violations: self.constraints.iter().map(|constraint| constraint.violation(&self.state)).collect(),
+            forces: self.constraint_impulses.iter().map(|impulse| impulse / dt).collect(),
+        };
+        self.constraint_impulses.fill(0.0);
+        report
+    }
+
+    // Clone scene with only native parts, so it can be simulated on another thread
+    pub(crate) fn try_clone(&self) -> PyResult<Self> {
+        // Python force generators and solvers need the GIL
+        if !self.force_generators.is_empty() {
+            return Err(PyValueError::new_err("Scene has python force generators, which need the GIL and can't be simulated in parallel (use Scene.simulate instead)"));
+        }
+        let ode_solver = self.ode_solver.box_clone()
+            .ok_or_else(|| PyValueError::new_err("Scene uses a python ODE solver, which needs the GIL and can't be simulated in parallel (use Scene.simulate instead)"))?;
+
+        // Clone scene
+        Ok(Scene {
+            gravity: self.gravity,
+            state: self.state.clone(),
+            ode_solver,
+            accelerations: self.accelerations.clone(),
+            data_collector: self.data_collector.clone(),
+            native_forces: self.native_forces.iter().map(|native_force| native_force.box_clone()).collect(),
+            force_generators: Vec::new(),
+            force_order: self.force_order.clone(),
+            constraints: self.constraints.iter().map(|constraint| constraint.box_clone()).collect(),
+            constraint_impulses: self.constraint_impulses.clone(),
+            constraint_iterations: self.constraint_iterations,
+            trajectories: self.trajectories.clone(),
+            collisions: self.collisions.clone(),
+        })
+    }
+
+    // Update scene (returns energies averaged over substeps)
+    pub(crate) fn update(self_: &Py<Self>, dt: Float, substeps: usize, py: Python) -> Energies {
         // Simulate substeps
-        let mut energy = 0.0;
+        let mut energies = Energies::default();
         for _ in 0..substeps {
-            // Apply accelerations to the scene
-            energy += Self::apply_accelerations(self_, py);
+            // Update scene objects
+            Self::update_objects(self_, dt / substeps as Float, py);
+
+            // Calculate energy
+            #[cfg(not(feature="no-energy"))]
+            energies.add(&Self::energies(self_, py));
+        }
+
+        // Return energies
+        energies.scale(1.0 / substeps as Float);
+        energies
+    }
 
+    // Update scene with only native force generators (doesn't need python)
+    pub(crate) fn update_native(&mut self, dt: Float, substeps: usize) -> Energies {
+        // Simulate substeps
+        let mut energies = Energies::default();
+        for _ in 0..substeps {
             // Update scene objects
-            {
-                let mut self_mut = self_.try_borrow_mut(py).unwrap();
-                energy += self_mut.update_objects(dt / substeps as Float);
-            }
+            self.update_objects_native(dt / substeps as Float);
+
+            // Calculate energy
+            #[cfg(not(feature="no-energy"))]
+            energies.add(&self.native_energies());
         }
 
-        // Return energy
-        energy
+        // Return energies
+        energies.scale(1.0 / substeps as Float);
+        energies
     }
 
     // Update scene objects
-    pub fn update_objects(&mut self, dt: Float) -> Float {
+    pub fn update_objects(self_: &Py<Self>, dt: Float, py: Python) {
+        // Take solver and state out of the scene, so force generators can borrow it while solving
+        // (positions at the start of the substep are kept for swept collisions, evaluations move the state)
+        let (mut ode_solver, mut positions, mut velocities, previous, power, targets, accelerations) = {
+            let mut self_mut = self_.try_borrow_mut(py).unwrap();
+            let scene = &mut *self_mut;
+            let targets = Self::drive_kinematic(&scene.trajectories, &mut scene.state, dt);
+            let ode_solver = std::mem::replace(&mut self_mut.ode_solver, Box::new(EulerODE));
+            let power = Self::native_dissipation(&self_mut.native_forces, &self_mut.state);
+            let (positions, velocities) = self_mut.state.coordinates();
+            let previous = self_mut.state.positions.clone();
+            (ode_solver, positions, velocities, previous, power, targets, self_mut.accelerations.take())
+        };
+
+        // Evaluate accelerations for the current state (unless the previous substep left them)
+        let mut accelerations = accelerations.unwrap_or_else(|| {
+            let mut accelerations = DVector::zeros(positions.len());
+            Self::evaluate(self_, py, &positions, &velocities, &mut accelerations);
+            accelerations
+        });
+
+        // Pass magnetic gyration frequencies for the current state to the solver
+        ode_solver.set_gyrofrequencies(&self_.try_borrow(py).unwrap().state.gyrofrequencies());
+
         // Use ODE solver to update objects
-        self.ode_solver.solve(dt, &mut self.positions, &mut self.velocities, &mut self.accelerations);
+        ode_solver.solve_with_jacobian(dt, &mut positions, &mut velocities, &mut accelerations, &mut |positions, velocities, accelerations| {
+            Self::evaluate(self_, py, positions, velocities, accelerations);
+        }, &mut |positions, velocities, da_dx, da_dv| {
+            Self::jacobians(self_, py, positions, velocities, da_dx, da_dv);
+        });
+
+        // Put solver and new state back into the scene
+        let mut self_mut = self_.try_borrow_mut(py).unwrap();
+        let scene = &mut *self_mut;
+        scene.ode_solver = ode_solver;
+        scene.finish_substep(dt, &positions, &velocities, accelerations, &previous, &targets, power);
+    }
 
-        // Calculate energy
-        let mut energy = 0.0;
-        #[cfg(not(feature="no-energy"))]
-        for i in 0..self.positions.len() / 2 {
-            if self.masses[i] == 0.0 {
-                continue;
+    // Update scene objects with only native force generators
+    fn update_objects_native(&mut self, dt: Float) {
+        let Scene { gravity, state, ode_solver, native_forces, trajectories, .. } = self;
+
+        // Move kinematic masses towards their next position
+        let targets = Self::drive_kinematic(trajectories, state, dt);
+        let (mut positions, mut velocities) = state.coordinates();
+
+        // Positions at the start of the substep for swept collisions (evaluations move the state)
+        let previous = state.positions.clone();
+
+        // Evaluate accelerations for the current state (unless the previous substep left them)
+        let mut accelerations = self.accelerations.take().unwrap_or_else(|| {
+            let mut accelerations = DVector::zeros(positions.len());
+            Self::evaluate_native(gravity, native_forces, state, &positions, &velocities, &mut accelerations);
+            accelerations
+        });
+
+        // Pass magnetic gyration frequencies for the current state to the solver
+        ode_solver.set_gyrofrequencies(&state.gyrofrequencies());
+
+        // Power dissipated at the start of the substep
+        let power = Self::native_dissipation(native_forces, state);
+
+        // Use ODE solver to update objects (both callbacks move the shared state)
+        let shared_state = RefCell::new(&mut *state);
+        ode_solver.solve_with_jacobian(dt, &mut positions, &mut velocities, &mut accelerations, &mut |positions, velocities, accelerations| {
+            Self::evaluate_native(gravity, native_forces, &mut shared_state.borrow_mut(), positions, velocities, accelerations);
+        }, &mut |positions, velocities, da_dx, da_dv| {
+            // Forces provide jacobians only for point masses, bodies are approximated with finite differences
+            if !shared_state.borrow().bodies.is_empty() {
+                finite_difference_jacobian(&mut |positions, velocities, accelerations| {
+                    Self::evaluate_native(gravity, native_forces, &mut shared_state.borrow_mut(), positions, velocities, accelerations);
+                }, positions, velocities, da_dx, da_dv);
+                return;
             }
+            let mut state = shared_state.borrow_mut();
+            state.positions.copy_from(positions);
+            state.velocities.copy_from(velocities);
+            da_dx.fill(0.0);
+            da_dv.fill(0.0);
+            for native_force in native_forces.iter() {
+                native_force.add_jacobian(&state, da_dx, da_dv);
+            }
+        });
 
-            // Ek = 1/2 * m * v^2
-            energy += 0.5 * self.masses[i] * (self.velocities[i * 2].powi(2) + self.velocities[i * 2 + 1].powi(2));
+        // Store new state and finish the substep
+        self.finish_substep(dt, &positions, &velocities, accelerations, &previous, &targets, power);
+    }
+
+    // Store solved state and do the work after the solver step shared by python and native updates
+    // (accelerations are the ones left by the solver, previous positions are at the start of the substep, power is dissipated at the start of it)
+    #[allow(clippy::too_many_arguments)]
+    fn finish_substep(&mut self, dt: Float, positions: &DVector<Float>, velocities: &DVector<Float>, accelerations: DVector<Float>, previous: &DVector<Float>, targets: &[(usize, Vector2<Float>)], power: Float) {
+        let Scene { state, ode_solver, native_forces, constraints, constraint_impulses, constraint_iterations, collisions, .. } = self;
+
+        // Store new state
+        state.set_coordinates(positions, velocities);
+        Self::place_kinematic(state, targets);
+
+        // Resolve collisions (at the end of the substep)
+        let time = state.time + dt;
+        let (collided, bounced) = collisions.resolve(state, previous, time, dt);
+
+        // Enforce constraints
+        let projected = constraint::project(constraints, state, constraint_impulses, *constraint_iterations, dt);
+
+        // Solver history doesn't continue through collisions and constraint corrections
+        if bounced || projected {
+            ode_solver.reset();
+        }
+
+        // Bring masses that left a periodic domain back
+        let wrapped = state.wrap_positions();
+
+        // Accelerations left by the solver start the next substep, if nothing moved the state after the step
+        let moved = bounced || projected || wrapped || !targets.is_empty();
+        self.accelerations = (ode_solver.leaves_accelerations() && !moved).then_some(accelerations);
+
+        // Integrate dissipated energy with the trapezoidal rule
+        #[cfg(not(feature="no-energy"))]
+        {
+            state.dissipated_energy += 0.5 * dt * (power + Self::native_dissipation(native_forces, state)) + collided;
         }
 
+        // Advance time
+        state.time += dt;
+
         // Reset accelerations
-        self.accelerations.fill(0.0);
+        state.clear_accelerations();
+    }
+
+    // Get energies of the current state
+    fn energies(self_: &Py<Self>, py: Python) -> Energies {
+        // Native force generators and gravity
+        let (mut energies, force_generators, force_order) = {
+            let self_ref = self_.try_borrow(py).unwrap();
+            (self_ref.native_energies(), self_ref.force_generators.clone(), self_ref.force_order.clone())
+        };
+
+        // Python force generators
+        for (energy, slot) in energies.potentials.iter_mut().zip(force_order) {
+            if let ForceSlot::Python(i) = slot {
+                let energy_result = force_generators[i].call_method0(py, intern!(py, "get_energy"));
+                if let Ok(energy_result) = energy_result {
+                    *energy = energy_result.extract::<Float>(py).unwrap();
+                }
+            }
+        }
 
-        // Return energy
-        energy
+        // Return energies
+        energies
     }
 
-    // Apply accelerations to the scene
-    pub fn apply_accelerations(self_: &Py<Self>, py: Python) -> Float {
-        // Apply gravity
+    // Get energies of the current state without python force generators (their potentials are left zero)
+    fn native_energies(&self) -> Energies {
+        Energies {
+            kinetic: self.state.kinetic_energy(),
+            // U = -m * g . x
+            // (pinned masses don't feel gravity, so it doesn't count for them)
+            gravity: (0..self.state.masses.len())
+                .filter(|&i| self.state.inverse_masses[i] != 0.0)
+                .map(|i| -self.state.masses[i] * self.gravity.y * self.state.positions[i * 2 + 1]).sum(),
+            potentials: self.force_order.iter().map(|slot| match slot {
+                ForceSlot::Native(i) => self.native_forces[*i].energy(&self.state),
+                ForceSlot::Python(_) => 0.0,
+            }).collect(),
+            dissipated: self.state.dissipated_energy,
+        }
+    }
+
+    // Evaluate accelerations for given state
+    pub fn evaluate(self_: &Py<Self>, py: Python, positions: &DVector<Float>, velocities: &DVector<Float>, accelerations: &mut DVector<Float>) {
+        // Move scene into the requested state
         {
             let mut self_mut = self_.try_borrow_mut(py).unwrap();
-            for i in 0..self_mut.positions.len() / 2 {
-                if self_mut.masses[i] == 0.0 {
-                    continue;
+            self_mut.state.set_coordinates(positions, velocities);
+            self_mut.state.clear_accelerations();
+            self_mut.state.magnetic_fields.fill(0.0);
+        }
+
+        // Apply accelerations to the scene
+        Self::apply_accelerations(self_, py);
+
+        // Read accelerations
+        self_.try_borrow(py).unwrap().state.read_accelerations(accelerations);
+    }
+
+    // Evaluate accelerations of native force generators for given state
+    fn evaluate_native(gravity: &Vector2<Float>, native_forces: &[Box<dyn NativeForce>], state: &mut State, positions: &DVector<Float>, velocities: &DVector<Float>, accelerations: &mut DVector<Float>) {
+        // Move state into the requested one
+        state.set_coordinates(positions, velocities);
+        state.clear_accelerations();
+        state.magnetic_fields.fill(0.0);
+
+        // Apply accelerations
+        Self::apply_native_accelerations(gravity, native_forces, state);
+
+        // Read accelerations
+        state.read_accelerations(accelerations);
+    }
+
+    // Evaluate jacobians of accelerations for given state
+    pub fn jacobians(self_: &Py<Self>, py: Python, positions: &DVector<Float>, velocities: &DVector<Float>, da_dx: &mut DMatrix<Float>, da_dv: &mut DMatrix<Float>) {
+        // Forces provide jacobians only for point masses, bodies are approximated with finite differences
+        if !self_.try_borrow(py).unwrap().state.bodies.is_empty() {
+            finite_difference_jacobian(&mut |positions, velocities, accelerations| {
+                Self::evaluate(self_, py, positions, velocities, accelerations);
+            }, positions, velocities, da_dx, da_dv);
+            return;
+        }
+
+        // Native force generators provide their own jacobians (gravity is constant)
+        da_dx.fill(0.0);
+        da_dv.fill(0.0);
+        let python_generators = {
+            let mut self_mut = self_.try_borrow_mut(py).unwrap();
+            let scene = &mut *self_mut;
+            scene.state.positions.copy_from(positions);
+            scene.state.velocities.copy_from(velocities);
+            for native_force in scene.native_forces.iter() {
+                native_force.add_jacobian(&scene.state, da_dx, da_dv);
+            }
+            scene.force_generators.clone()
+        };
+
+        // Python force generators are approximated with finite differences
+        if !python_generators.is_empty() {
+            let n = positions.len();
+            let mut python_da_dx = DMatrix::zeros(n, n);
+            let mut python_da_dv = DMatrix::zeros(n, n);
+            finite_difference_jacobian(&mut |positions, velocities, accelerations| {
+                {
+                    let mut self_mut = self_.try_borrow_mut(py).unwrap();
+                    self_mut.state.positions.copy_from(positions);
+                    self_mut.state.velocities.copy_from(velocities);
+                    self_mut.state.accelerations.fill(0.0);
                 }
-                self_mut.accelerations[i * 2 + 1] += self_mut.gravity.y;
+                Self::apply_force_generators(py, &python_generators);
+                accelerations.copy_from(&self_.try_borrow(py).unwrap().state.accelerations);
+            }, positions, velocities, &mut python_da_dx, &mut python_da_dv);
+            *da_dx += python_da_dx;
+            *da_dv += python_da_dv;
+        }
+    }
+
+    // Apply accelerations to the scene
+    pub fn apply_accelerations(self_: &Py<Self>, py: Python) {
+        // Apply gravity and native force generators
+        let force_generators = {
+            let mut self_mut = self_.try_borrow_mut(py).unwrap();
+            let scene = &mut *self_mut;
+            Self::apply_native_accelerations(&scene.gravity, &scene.native_forces, &mut scene.state);
+            scene.force_generators.clone()
+        };
+
+        // Apply python force generators
+        Self::apply_force_generators(py, &force_generators);
+    }
+
+    // Apply gravity and native force generators to the state
+    fn apply_native_accelerations(gravity: &Vector2<Float>, native_forces: &[Box<dyn NativeForce>], state: &mut State) {
+        // Apply gravity
+        for i in 0..state.positions.len() / 2 {
+            if state.inverse_masses[i] == 0.0 {
+                continue;
             }
+            state.accelerations[i * 2 + 1] += gravity.y;
         }
 
-        // Apply force generators
-        let mut energy = 0.0;
-        let force_generators = self_.try_borrow(py).unwrap().force_generators.clone();
+        // Apply native force generators
+        for native_force in native_forces.iter() {
+            native_force.apply(state);
+        }
+    }
+
+    // Set velocities of kinematic masses to reach their trajectories at the end of the substep, returns their positions there
+    fn drive_kinematic(trajectories: &[(usize, Arc<TrajectoryFn>)], state: &mut State, dt: Float) -> Vec<(usize, Vector2<Float>)> {
+        trajectories.iter().map(|(index, trajectory)| {
+            let target = trajectory(state.time + dt);
+            let velocity = state.minimum_image(target - state.position(*index)) / dt;
+            state.velocities[index * 2] = velocity.x;
+            state.velocities[index * 2 + 1] = velocity.y;
+            (*index, target)
+        }).collect()
+    }
+
+    // Put kinematic masses exactly on their trajectories
+    fn place_kinematic(state: &mut State, targets: &[(usize, Vector2<Float>)]) {
+        for &(index, target) in targets.iter() {
+            state.positions[index * 2] = target.x;
+            state.positions[index * 2 + 1] = target.y;
+        }
+    }
+
+    // Get power dissipated by native force generators
+    fn native_dissipation(native_forces: &[Box<dyn NativeForce>], state: &State) -> Float {
+        native_forces.iter().map(|native_force| native_force.dissipation(state)).sum()
+    }
+
+    // Apply given force generators to the scene
+    fn apply_force_generators(py: Python, force_generators: &[PyObject]) {
         for force_generator in force_generators.iter() {
             // Apply forces
             let result = force_generator.call_method0(py, intern!(py, "apply_force"));
 
-            // Calculate energy
-            #[cfg(not(feature="no-energy"))]
-            {
-                let energy_result = force_generator.call_method0(py, intern!(py, "get_energy"));
-                if energy_result.is_ok() {
-                    energy += energy_result.unwrap().extract::<Float>(py).unwrap();
-                }
-            }
-
             // Check for errors while applying force
             if result.is_err() {
                 panic!("Error while applying force: {:?}", result);
             }
         }
-
-        // Return energy
-        energy
     }
 }
 
 // Reference to a mass
-#[pyclass]
+#[pyclass(subclass)]
 pub struct MassRef {
     // Scene pointer
-    scene: Py<Scene>,
+    pub(crate) scene: Py<Scene>,
 
     // Index of the mass in the scene vectors
     pub(crate) index: usize
@@ -276,8 +1124,10 @@ impl MassRef {
             let mut scene = self_.scene.borrow_mut(py);
 
             // Update position
-            scene.positions[self_.index * 2] = position.0;
-            scene.positions[self_.index * 2 + 1] = position.1;
+            scene.state.set_position(self_.index, Vector2::new(position.0, position.1));
+
+            // Solver history is no longer valid
+            scene.reset_solver();
         }
 
         // Return position
@@ -292,8 +1142,11 @@ impl MassRef {
             let mut scene = self_.scene.borrow_mut(py);
 
             // Update position
-            scene.positions[self_.index * 2] = scene.positions[origin.index * 2];
-            scene.positions[self_.index * 2 + 1] = scene.positions[origin.index * 2 + 1];
+            let position = scene.state.position(origin.index);
+            scene.state.set_position(self_.index, position);
+
+            // Solver history is no longer valid
+            scene.reset_solver();
         }
 
         // Return position
@@ -309,8 +1162,11 @@ impl MassRef {
 
             let angle = if deg.unwrap_or(false) { angle.to_radians() } else { angle };
             // Update position
-            scene.positions[self_.index * 2] = scene.positions[origin.index * 2] + dist * angle.cos();
-            scene.positions[self_.index * 2 + 1] = scene.positions[origin.index * 2 + 1] + dist * angle.sin();
+            let position = scene.state.position(origin.index) + Vector2::new(dist * angle.cos(), dist * angle.sin());
+            scene.state.set_position(self_.index, position);
+
+            // Solver history is no longer valid
+            scene.reset_solver();
         }
 
         // Return position
@@ -326,8 +1182,10 @@ impl MassRef {
 
             let angle = if deg.unwrap_or(false) { angle.to_radians() } else { angle };
             // Update position
-            scene.positions[self_.index * 2] = origin.0 + dist * angle.cos();
-            scene.positions[self_.index * 2 + 1] = origin.1 + dist * angle.sin();
+            scene.state.set_position(self_.index, Vector2::new(origin.0 + dist * angle.cos(), origin.1 + dist * angle.sin()));
+
+            // Solver history is no longer valid
+            scene.reset_solver();
         }
 
         // Return position
@@ -342,8 +1200,11 @@ impl MassRef {
             let mut scene = self_.scene.borrow_mut(py);
 
             // Update velocity
-            scene.velocities[self_.index * 2] = velocity.0;
-            scene.velocities[self_.index * 2 + 1] = velocity.1;
+            scene.state.velocities[self_.index * 2] = velocity.0;
+            scene.state.velocities[self_.index * 2 + 1] = velocity.1;
+
+            // Solver history is no longer valid
+            scene.reset_solver();
         }
 
         // Return position
@@ -357,8 +1218,53 @@ impl MassRef {
             // Get scene
             let mut scene = self_.scene.borrow_mut(py);
 
+            // Negative masses are not physical, pinning is the way to make a mass immovable
+            if mass < 0.0 || !mass.is_finite() {
+                return Err(PyValueError::new_err(format!("Mass must be finite and non-negative, got {} (use pin() to fix a mass in place)", mass)));
+            }
+
             // Update mass
-            scene.masses[self_.index] = mass;
+            scene.state.masses[self_.index] = mass;
+            scene.state.update_inverse_mass(self_.index);
+
+            // Solver history is no longer valid
+            scene.reset_solver();
+        }
+
+        // Return position
+        Ok(self_)
+    }
+
+    // Set radius (masses with a radius collide with each other)
+    fn radius<'a>(self_: PyRef<'a, Self>, py: Python, radius: Float) -> PyResult<PyRef<'a, Self>> {
+        // Wrap in a block to release the borrow of scene
+        {
+            // Get scene
+            let mut scene = self_.scene.borrow_mut(py);
+
+            // Update radius
+            if radius < 0.0 || !radius.is_finite() {
+                return Err(PyValueError::new_err(format!("Radius must be finite and non-negative, got {}", radius)));
+            }
+            scene.state.radii[self_.index] = radius;
+        }
+
+        // Return position
+        Ok(self_)
+    }
+
+    // Set charge
+    fn charge<'a>(self_: PyRef<'a, Self>, py: Python, charge: Float) -> PyResult<PyRef<'a, Self>> {
+        // Wrap in a block to release the borrow of scene
+        {
+            // Get scene
+            let mut scene = self_.scene.borrow_mut(py);
+
+            // Update charge
+            scene.state.charges[self_.index] = charge;
+
+            // Solver history is no longer valid
+            scene.reset_solver();
         }
 
         // Return position
@@ -372,15 +1278,95 @@ impl MassRef {
             // Get scene
             let mut scene = self_.scene.borrow_mut(py);
 
-            // Update acceleration
-            scene.accelerations[self_.index * 2] += force.0 / scene.masses[self_.index];
-            scene.accelerations[self_.index * 2 + 1] += force.1 / scene.masses[self_.index];
+            // Update acceleration (pinned and zero mass objects don't move)
+            scene.state.apply_force(self_.index, Vector2::new(force.0, force.1));
         }
 
         // Return position
         Ok(self_)
     }
 
+    // Fix mass in place, it is no longer moved by forces or constraints
+    fn pin<'a>(self_: PyRef<'a, Self>, py: Python) -> PyResult<PyRef<'a, Self>> {
+        // Wrap in a block to release the borrow of scene
+        {
+            // Get scene
+            let mut scene = self_.scene.borrow_mut(py);
+            let index = self_.index;
+
+            // Stop mass and forget its trajectory
+            scene.trajectories.retain(|(i, _)| *i != index);
+            scene.state.velocities[index * 2] = 0.0;
+            scene.state.velocities[index * 2 + 1] = 0.0;
+            scene.state.pinned[index] = true;
+            scene.state.update_inverse_mass(index);
+
+            // Solver history is no longer valid
+            scene.reset_solver();
+        }
+
+        // Return position
+        Ok(self_)
+    }
+
+    // Release pinned or kinematic mass, it keeps its current velocity
+    fn unpin<'a>(self_: PyRef<'a, Self>, py: Python) -> PyResult<PyRef<'a, Self>> {
+        // Wrap in a block to release the borrow of scene
+        {
+            // Get scene
+            let mut scene = self_.scene.borrow_mut(py);
+            let index = self_.index;
+
+            // Release mass
+            scene.trajectories.retain(|(i, _)| *i != index);
+            scene.state.pinned[index] = false;
+            scene.state.update_inverse_mass(index);
+
+            // Solver history is no longer valid
+            scene.reset_solver();
+        }
+
+        // Return position
+        Ok(self_)
+    }
+
+    // Move mass along a prescribed trajectory given by a function of time, it acts as pinned for forces and constraints
+    fn kinematic<'a>(self_: PyRef<'a, Self>, py: Python, trajectory: PyObject) -> PyResult<PyRef<'a, Self>> {
+        // Get current position on the trajectory (scene is not borrowed while calling python)
+        let time = self_.scene.borrow(py).state.time;
+        let position: Vec2 = trajectory.call1(py, (time,))?.extract(py)?;
+
+        // Wrap in a block to release the borrow of scene
+        {
+            // Get scene
+            let mut scene = self_.scene.borrow_mut(py);
+            let index = self_.index;
+
+            // Place mass on the trajectory
+            scene.state.set_position(index, Vector2::new(position.0, position.1));
+            scene.state.velocities[index * 2] = 0.0;
+            scene.state.velocities[index * 2 + 1] = 0.0;
+            scene.state.pinned[index] = true;
+            scene.state.update_inverse_mass(index);
+
+            // Replace previous trajectory
+            scene.trajectories.retain(|(i, _)| *i != index);
+            scene.trajectories.push((index, python_trajectory(trajectory)));
+
+            // Solver history is no longer valid
+            scene.reset_solver();
+        }
+
+        // Return position
+        Ok(self_)
+    }
+
+    // Pinned getter (true for kinematic masses too)
+    #[getter(pinned)]
+    fn get_pinned(self_: PyRef<Self>, py: Python) -> bool {
+        self_.scene.borrow(py).state.pinned[self_.index]
+    }
+
     // Position getter
     #[getter(position)]
     fn get_position(self_: PyRef<Self>, py: Python) -> PyResult<(Float, Float)> {
@@ -388,7 +1374,7 @@ impl MassRef {
         let scene = self_.scene.borrow(py);
 
         // Return position
-        Ok((scene.positions[self_.index * 2], scene.positions[self_.index * 2 + 1]))
+        Ok((scene.state.positions[self_.index * 2], scene.state.positions[self_.index * 2 + 1]))
     }
 
     // Get distance to another mass
@@ -396,8 +1382,8 @@ impl MassRef {
         // Get scene
         let scene = self_.scene.borrow(py);
 
-        // Return distance
-        Ok(((scene.positions[self_.index * 2] - scene.positions[other.index * 2]).powi(2) + (scene.positions[self_.index * 2 + 1] - scene.positions[other.index * 2 + 1]).powi(2)).sqrt())
+        // Return distance (through the boundaries of a periodic domain)
+        Ok(scene.state.separation(self_.index, other.index).norm())
     }
 
     // Velocity getter
@@ -407,7 +1393,7 @@ impl MassRef {
         let scene = self_.scene.borrow(py);
 
         // Return velocity
-        Ok((scene.velocities[self_.index * 2], scene.velocities[self_.index * 2 + 1]))
+        Ok((scene.state.velocities[self_.index * 2], scene.state.velocities[self_.index * 2 + 1]))
     }
 
     // Index getter
@@ -425,7 +1411,7 @@ impl MassRef {
         let scene = self.scene.borrow(py);
 
         // Return position
-        (scene.positions[self.index * 2], scene.positions[self.index * 2 + 1])
+        (scene.state.positions[self.index * 2], scene.state.positions[self.index * 2 + 1])
     }
 
     // Get velocity
@@ -434,7 +1420,7 @@ impl MassRef {
         let scene = self.scene.borrow(py);
 
         // Return velocity
-        (scene.velocities[self.index * 2], scene.velocities[self.index * 2 + 1])
+        (scene.state.velocities[self.index * 2], scene.state.velocities[self.index * 2 + 1])
     }
 }
 
@@ -444,36 +1430,492 @@ impl MassRef {
     pub fn raw_position(&self, py: Python) -> Vector2<Float> {
         let scene = self.scene.borrow(py);
         // Return position
-        Vector2::new(scene.positions[self.index * 2], scene.positions[self.index * 2 + 1])
+        Vector2::new(scene.state.positions[self.index * 2], scene.state.positions[self.index * 2 + 1])
     }
 
     // Get velocity
     pub fn raw_velocity(&self, py: Python) -> Vector2<Float> {
         let scene = self.scene.borrow(py);
         // Return velocity
-        Vector2::new(scene.velocities[self.index * 2], scene.velocities[self.index * 2 + 1])
+        Vector2::new(scene.state.velocities[self.index * 2], scene.state.velocities[self.index * 2 + 1])
     }
 
     // Raw mass
     pub fn raw_mass(&self, py: Python) -> Float {
         let scene = self.scene.borrow(py);
         // Return mass
-        scene.masses[self.index]
+        scene.state.masses[self.index]
+    }
+
+    // Raw charge
+    pub fn raw_charge(&self, py: Python) -> Float {
+        let scene = self.scene.borrow(py);
+        // Return charge
+        scene.state.charges[self.index]
+    }
+
+    // Add magnetic field acting on the mass
+    pub fn raw_add_magnetic_field(&self, py: Python, field: Float) {
+        let mut scene = self.scene.borrow_mut(py);
+        scene.state.magnetic_fields[self.index] += field;
     }
 
     // Apply force
     pub fn raw_apply_force(&self, py: Python, force: Vector2<Float>) {
         let mut scene = self.scene.borrow_mut(py);
-        // Check if force is NaN
-        if force.x.is_nan() || force.y.is_nan() {
-            panic!("Force is NaN");
+        scene.state.apply_force(self.index, force);
+    }
+}
+
+// Reference to a rigid body (mass at its centre with an orientation)
+#[pyclass(extends=MassRef)]
+pub struct BodyRef {
+    // Index of the body in the scene vectors
+    pub(crate) body: usize,
+}
+
+// BodyRef class implementation (python)
+#[pymethods]
+impl BodyRef {
+    // Set angle (counter-clockwise, in radians unless deg is set)
+    fn angle<'a>(self_: PyRef<'a, Self>, py: Python, angle: Float, deg: Option<bool>) -> PyResult<PyRef<'a, Self>> {
+        // Wrap in a block to release the borrow of scene
+        {
+            // Get scene
+            let mut scene = self_.as_ref().scene.borrow_mut(py);
+
+            // Update angle
+            scene.state.angles[self_.body] = if deg.unwrap_or(false) { angle.to_radians() } else { angle };
+
+            // Solver history is no longer valid
+            scene.reset_solver();
         }
 
-        // Apply force (if mass is not zero)
-        if scene.masses[self.index] == 0.0 {
-            return;
+        // Return body
+        Ok(self_)
+    }
+
+    // Set angular velocity (counter-clockwise, in radians per second)
+    fn angular_vel<'a>(self_: PyRef<'a, Self>, py: Python, angular_velocity: Float) -> PyResult<PyRef<'a, Self>> {
+        // Wrap in a block to release the borrow of scene
+        {
+            // Get scene
+            let mut scene = self_.as_ref().scene.borrow_mut(py);
+
+            // Update angular velocity
+            scene.state.angular_velocities[self_.body] = angular_velocity;
+
+            // Solver history is no longer valid
+            scene.reset_solver();
         }
-        scene.accelerations[self.index * 2] += force.x / scene.masses[self.index];
-        scene.accelerations[self.index * 2 + 1] += force.y / scene.masses[self.index];
+
+        // Return body
+        Ok(self_)
+    }
+
+    // Set moment of inertia (bodies with zero moment of inertia don't rotate)
+    fn inertia<'a>(self_: PyRef<'a, Self>, py: Python, inertia: Float) -> PyResult<PyRef<'a, Self>> {
+        // Wrap in a block to release the borrow of scene
+        {
+            // Get scene
+            let mut scene = self_.as_ref().scene.borrow_mut(py);
+
+            // Update moment of inertia
+            if inertia < 0.0 || !inertia.is_finite() {
+                return Err(PyValueError::new_err(format!("Moment of inertia must be finite and non-negative, got {}", inertia)));
+            }
+            scene.state.inertias[self_.body] = inertia;
+            scene.state.update_inverse_inertia(self_.body);
+
+            // Solver history is no longer valid
+            scene.reset_solver();
+        }
+
+        // Return body
+        Ok(self_)
+    }
+
+    // Apply force at a point (in scene coordinates), which also applies torque around the centre
+    fn apply_force_at<'a>(self_: PyRef<'a, Self>, py: Python, force: Vec2, point: Vec2) -> PyResult<PyRef<'a, Self>> {
+        // Wrap in a block to release the borrow of scene
+        {
+            // Get scene
+            let mut scene = self_.as_ref().scene.borrow_mut(py);
+
+            // Update accelerations
+            let offset = scene.state.minimum_image(Vector2::new(point.0, point.1) - scene.state.position(self_.as_ref().index));
+            scene.state.apply_force_at(self_.body, Vector2::new(force.0, force.1), offset);
+        }
+
+        // Return body
+        Ok(self_)
+    }
+
+    // Apply torque (counter-clockwise)
+    fn apply_torque<'a>(self_: PyRef<'a, Self>, py: Python, torque: Float) -> PyResult<PyRef<'a, Self>> {
+        // Update angular acceleration
+        self_.as_ref().scene.borrow_mut(py).state.apply_torque(self_.body, torque);
+
+        // Return body
+        Ok(self_)
+    }
+
+    // Get position of a point fixed to the body (given in its local frame)
+    fn point(self_: PyRef<Self>, py: Python, local: Vec2) -> PyResult<(Float, Float)> {
+        // Get scene
+        let scene = self_.as_ref().scene.borrow(py);
+
+        // Return position
+        let point = scene.state.position(self_.as_ref().index) + scene.state.body_offset(self_.body, Vector2::new(local.0, local.1));
+        Ok((point.x, point.y))
+    }
+
+    // Orientation getter
+    #[getter(orientation)]
+    fn get_orientation(self_: PyRef<Self>, py: Python) -> Float {
+        self_.as_ref().scene.borrow(py).state.angles[self_.body]
+    }
+
+    // Angular velocity getter
+    #[getter(angular_velocity)]
+    fn get_angular_velocity(self_: PyRef<Self>, py: Python) -> Float {
+        self_.as_ref().scene.borrow(py).state.angular_velocities[self_.body]
+    }
+
+    // Moment of inertia getter
+    #[getter(moment_of_inertia)]
+    fn get_moment_of_inertia(self_: PyRef<Self>, py: Python) -> Float {
+        self_.as_ref().scene.borrow(py).state.inertias[self_.body]
+    }
+
+    // Body index getter
+    #[getter(body_index)]
+    fn get_body_index(self_: PyRef<Self>) -> usize {
+        self_.body
+    }
+}
+
+// Test module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{sync::{Mutex, mpsc}, time::Duration};
+    use crate::{solvers::ODESolver, collider::{Collider, Shape}, test_utils::{with_python, run_python}};
+
+    // Builds scenes with native forces, collisions and a constraint (dispatched ones also have a python force generator doing nothing,
+    // solver can also be written in python)
+    const SCENES: &str = r#"
+class Idle:
+    def apply_force(self): pass
+    def get_energy(self): return 0.0
+
+class Euler(pyphyrs.solvers.ODESolver):
+    def solve(self, dt, positions, velocities, accelerations):
+        for i in range(len(positions)):
+            positions[i] += velocities[i] * dt
+            velocities[i] += accelerations[i] * dt
+
+def build(dispatched=False, ode="rk4"):
+    scene = pyphyrs.Scene(ode=ode)
+    masses = [scene.mass().at((i, 2.0 + 0.1 * i)).vel((0.0, -i)).radius(0.3) for i in range(5)]
+    masses[0].pin()
+    for a, b in zip(masses, masses[1:-1]):
+        scene.add_force(pyphyrs.force.SpringForce(a, b, 40.0, 0.8, damping=0.5))
+    scene.add_force(pyphyrs.force.DragForce(masses, linear=0.1))
+    scene.add_constraint(pyphyrs.constraint.Distance(masses[3], masses[4]))
+    scene.add_collider(pyphyrs.collider.Plane((0, 0), (0, 1)))
+    if dispatched:
+        scene.add_force(Idle())
+    return scene
+"#;
+
+    // Build scene using given python expression
+    fn build(py: Python, expression: &str) -> Py<Scene> {
+        let globals = run_python(py, SCENES);
+        py.eval(expression, Some(globals), None).unwrap().extract().unwrap()
+    }
+
+    // Check that two scenes are in exactly the same state
+    fn assert_same_state(a: &State, b: &State) {
+        assert_eq!(a.time, b.time);
+        assert_eq!(a.positions, b.positions);
+        assert_eq!(a.velocities, b.velocities);
+        assert_eq!(a.dissipated_energy, b.dissipated_energy);
+    }
+
+    // Ball of radius 0.1 dropped from a height of 1 on a floor
+    fn bouncing_ball(ode_solver: Box<dyn Send + ODESolver>) -> Scene {
+        let mut scene = Scene { ode_solver, ..Default::default() };
+        scene.add_mass();
+        scene.state.positions[1] = 1.0;
+        scene.state.radii[0] = 0.1;
+        scene.collisions.colliders.push(Collider {
+            shape: Shape::Plane { point: Vector2::zeros(), normal: Vector2::new(0.0, 1.0) },
+            restitution: None,
+            friction: None,
+        });
+        scene
+    }
+
+    #[test]
+    fn periodic_domain_wraps_positions_and_keeps_unwrapped_ones() {
+        let mut state = State {
+            positions: DVector::from_vec(vec![10.5, -0.25, 3.0, 1.0]),
+            unwrap_offsets: DVector::zeros(4),
+            domain: Some(Domain { min: Vector2::new(0.0, -1.0), max: Vector2::new(4.0, 1.0), periodic: true }),
+            ..Default::default()
+        };
+        state.wrap_positions();
+
+        // Positions are moved into the domain by whole periods, unwrapped ones stay where they were
+        assert_eq!(state.position(0), Vector2::new(2.5, -0.25));
+        assert_eq!(state.position(1), Vector2::new(3.0, -1.0));
+        assert_eq!(state.unwrapped_positions(), DVector::from_vec(vec![10.5, -0.25, 3.0, 1.0]));
+
+        // Separation goes through the nearest boundary
+        let d = state.separation(0, 1);
+        assert!((d - Vector2::new(-0.5, 0.75)).norm() < 1e-6);
+        assert!((state.separation(1, 0) + d).norm() < 1e-6);
+    }
+
+    #[test]
+    fn pinned_and_zero_mass_masses_ignore_forces() {
+        let mut state = State {
+            positions: DVector::zeros(6),
+            accelerations: DVector::zeros(6),
+            masses: DVector::from_vec(vec![2.0, 0.0, 2.0]),
+            inverse_masses: DVector::zeros(3),
+            pinned: vec![false, false, true],
+            ..Default::default()
+        };
+        for i in 0..3 {
+            state.update_inverse_mass(i);
+        }
+
+        // Only the free mass accelerates, the others stay without dividing by zero
+        for i in 0..3 {
+            state.apply_force(i, Vector2::new(1.0, -4.0));
+        }
+        assert_eq!(state.accelerations, DVector::from_vec(vec![0.5, -2.0, 0.0, 0.0, 0.0, 0.0]));
+
+        // Unpinned mass moves again
+        state.pinned[2] = false;
+        state.update_inverse_mass(2);
+        state.apply_force(2, Vector2::new(1.0, 0.0));
+        assert_eq!(state.accelerations[4], 0.5);
+    }
+
+    #[test]
+    fn bodies_rotate_through_the_solver() {
+        // Point mass and a body spun up by a constant torque
+        let mut state = State {
+            positions: DVector::zeros(4),
+            velocities: DVector::from_vec(vec![1.0, 0.0, 0.0, 0.0]),
+            accelerations: DVector::zeros(4),
+            masses: DVector::from_vec(vec![1.0, 1.0]),
+            inverse_masses: DVector::from_vec(vec![1.0, 1.0]),
+            bodies: vec![1],
+            angles: DVector::from_vec(vec![0.5]),
+            angular_velocities: DVector::from_vec(vec![2.0]),
+            angular_accelerations: DVector::zeros(1),
+            inertias: DVector::from_vec(vec![3.0]),
+            inverse_inertias: DVector::from_vec(vec![1.0 / 3.0]),
+            ..Default::default()
+        };
+
+        // Angles are solved together with positions
+        let (mut positions, mut velocities) = state.coordinates();
+        assert_eq!(positions.len(), 5);
+        let evaluate = |state: &mut State, positions: &DVector<Float>, velocities: &DVector<Float>, accelerations: &mut DVector<Float>| {
+            state.set_coordinates(positions, velocities);
+            state.clear_accelerations();
+            state.apply_torque(0, 1.5);
+            state.read_accelerations(accelerations);
+        };
+        let mut accelerations = DVector::zeros(5);
+        evaluate(&mut state, &positions, &velocities, &mut accelerations);
+        assert_eq!(accelerations[4], 0.5);
+        for _ in 0..100 {
+            solvers::RK4.solve(0.01, &mut positions, &mut velocities, &mut accelerations, &mut |p, v, a| evaluate(&mut state, p, v, a));
+        }
+        state.set_coordinates(&positions, &velocities);
+
+        // angle = angle0 + w0 * t + a * t^2 / 2, rotational energy counts as kinetic
+        assert!((state.angles[0] - 2.75).abs() < 1e-4);
+        assert!((state.position(0) - Vector2::new(1.0, 0.0)).norm() < 1e-4);
+        assert!((state.kinetic_energy() - (0.5 + 0.5 * 3.0 * 2.5 * 2.5)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn abm4_history_is_reset_by_bounces() {
+        let mut scene = bouncing_ball(Box::new(solvers::ABM4::new()));
+        let energy = |state: &State| 0.5 * state.velocity(0).norm_squared() + 9.81 * state.positions[1];
+        let initial_energy = energy(&scene.state);
+
+        // Several bounces, each one starts the multistep history again
+        let mut max_error: Float = 0.0;
+        for _ in 0..3000 {
+            scene.update_native(0.001, 1);
+            max_error = max_error.max((energy(&scene.state) - initial_energy).abs());
+        }
+        assert!(max_error < 0.005 * initial_energy, "energy error {}", max_error);
+    }
+
+    #[test]
+    fn native_simulation_matches_python_dispatch() {
+        with_python(|py| {
+            // Scene without python force generators is simulated natively without the GIL
+            let native = build(py, "build()");
+            let dispatched = build(py, "build(dispatched=True)");
+            for scene in [&native, &dispatched] {
+                let mut data_collector = InMemoryDataCollector::new();
+                Scene::simulate_frames(scene, &mut data_collector, 100, 4, 0.01, py);
+            }
+
+            // Both paths give the same trajectories
+            assert!((native.borrow(py).state.time - 1.0).abs() < 1e-4);
+            assert_same_state(&native.borrow(py).state, &dispatched.borrow(py).state);
+        });
+    }
+
+    #[test]
+    fn native_simulation_releases_the_gil() {
+        let (sender, receiver) = mpsc::channel();
+        let receiver = Mutex::new(receiver);
+        with_python(|py| {
+            // Kinematic mass waits for another thread to take the GIL while the scene is simulated
+            let mut scene = Scene::default();
+            let index = scene.add_mass();
+            scene.trajectories.push((index, Arc::new(move |_| {
+                receiver.lock().unwrap().recv_timeout(Duration::from_secs(10)).expect("GIL is held during native simulation");
+                Vector2::zeros()
+            })));
+            let scene = Py::new(py, scene).unwrap();
+            let thread = std::thread::spawn(move || Python::with_gil(|_| sender.send(()).unwrap()));
+            Scene::simulate_frames(&scene, &mut InMemoryDataCollector::new(), 1, 1, 0.01, py);
+            py.allow_threads(|| thread.join().unwrap());
+        });
+    }
+
+    #[test]
+    fn cloned_scene_simulates_like_the_original() {
+        with_python(|py| {
+            let original = build(py, "build()");
+            let mut clone = original.borrow(py).try_clone().unwrap();
+            clone.simulate_native(&mut InMemoryDataCollector::new(), 100, 4, 0.01);
+            Scene::simulate_frames(&original, &mut InMemoryDataCollector::new(), 100, 4, 0.01, py);
+            assert_same_state(&original.borrow(py).state, &clone.state);
+        });
+    }
+
+    #[test]
+    fn scenes_needing_python_are_not_cloned() {
+        with_python(|py| {
+            let error = |expression| build(py, expression).borrow(py).try_clone().err().unwrap().value(py).to_string();
+            assert!(error("build(dispatched=True)").starts_with("Scene has python force generators"));
+            assert!(error("build(ode=Euler())").starts_with("Scene uses a python ODE solver"));
+        });
+    }
+
+    #[test]
+    fn masses_do_not_tunnel_through_segments_with_multistage_solvers() {
+        with_python(|py| {
+            for ode_solver in [Box::new(solvers::VerletODE) as Box<dyn Send + ODESolver>, Box::new(solvers::RK4)] {
+                // Point mass crossing a wall within a single substep
+                let mut scene = Scene { gravity: Vector2::zeros(), ode_solver, ..Default::default() };
+                scene.add_mass();
+                scene.state.velocities[0] = 10.0;
+                scene.collisions.colliders.push(Collider {
+                    shape: Shape::Segment { a: Vector2::new(0.5, -1.0), b: Vector2::new(0.5, 1.0) },
+                    restitution: None,
+                    friction: None,
+                });
+                let dispatched = Py::new(py, scene.try_clone().unwrap()).unwrap();
+
+                // Both update paths stop the mass on the side it came from and bounce it back
+                scene.update_native(0.1, 1);
+                Scene::update(&dispatched, 0.1, 1, py);
+                for state in [&scene.state, &dispatched.borrow(py).state] {
+                    assert!(state.positions[0] <= 0.5, "mass went through the wall to {}", state.positions[0]);
+                    assert_eq!(state.velocity(0), Vector2::new(-10.0, 0.0));
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn solvers_reuse_accelerations_they_leave() {
+        with_python(|py| {
+            let globals = run_python(py, r#"
+class Counter:
+    calls = 0
+    def apply_force(self): self.calls += 1
+    def get_energy(self): return 0.0
+
+def evaluations(ode, height=1.0):
+    scene = pyphyrs.Scene(ode=ode)
+    scene.mass().at((0.0, height)).radius(0.1)
+    scene.add_collider(pyphyrs.collider.Plane((0, 0), (0, 1)))
+    counter = Counter()
+    scene.add_force(counter)
+    scene.step(0.1, 10)
+    return counter.calls
+"#);
+            let evaluations = |expression| py.eval(expression, Some(globals), None).unwrap().extract::<usize>().unwrap();
+
+            // Solvers which leave accelerations for the new state need one evaluation less per substep
+            assert_eq!(evaluations("evaluations('euler')"), 10);
+            assert_eq!(evaluations("evaluations('rk4')"), 40);
+            assert_eq!(evaluations("evaluations('verlet')"), 1 + 10);
+            assert_eq!(evaluations("evaluations('yoshida4')"), 1 + 3 * 10);
+
+            // Bounces change the state, so accelerations are evaluated again
+            assert!(evaluations("evaluations('verlet', height=0.1)") > 1 + 10);
+        });
+    }
+
+    #[test]
+    fn abm4_keeps_multistep_history_in_constrained_pendulum() {
+        with_python(|py| {
+            let globals = run_python(py, r#"
+class Counter:
+    calls = 0
+    def apply_force(self): self.calls += 1
+    def get_energy(self): return 0.0
+
+scene = pyphyrs.Scene(ode="abm4")
+anchor = scene.mass().pin()
+bob = scene.mass().at((1.0, 0.0))
+scene.add_constraint(pyphyrs.constraint.Distance(anchor, bob))
+counter = Counter()
+scene.add_force(counter)
+scene.step(1.0, 1000)
+"#);
+            let calls = globals.get_item("counter").unwrap().getattr("calls").unwrap().extract::<usize>().unwrap();
+
+            // Corrections of the constraint only remove drift, so after 3 RK4 substeps of the bootstrap each substep
+            // evaluates forces once (plus once for the first predicted step)
+            assert_eq!(calls, 3 * 4 + 997 + 1);
+        });
+    }
+
+    #[test]
+    fn solver_stats_come_from_the_scene_copy() {
+        with_python(|py| {
+            let globals = run_python(py, r#"
+solver = pyphyrs.solvers.RK45(rtol=1e-6, atol=1e-6)
+scene = pyphyrs.Scene(ode=solver)
+scene.mass().vel((1.0, 0.0))
+before = scene.solver_stats
+scene.step(1.0, 1)
+after = scene.solver_stats
+"#);
+            let stats = |name| globals.get_item(name).unwrap().extract::<(usize, usize)>().unwrap();
+
+            // Steps taken by the scene are counted, the solver object doesn't pretend to know them
+            assert_eq!(stats("before"), (0, 0));
+            assert!(stats("after").0 > 0);
+            assert!(!globals.get_item("solver").unwrap().hasattr("accepted_steps").unwrap());
+        });
     }
 }
\ No newline at end of file
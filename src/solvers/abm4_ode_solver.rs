@@ -0,0 +1,162 @@
+// Standard imports
+use std::collections::VecDeque;
+
+// External imports
+use nalgebra::DVector;
+
+// PyO3 imports
+use pyo3::prelude::*;
+
+// Crate imports
+use crate::Float;
+use super::{ODESolver, AccelerationFn, RK4};
+
+// Adams-Bashforth-Moulton 4th order predictor-corrector solver implementation
+#[pyclass]
+#[derive(Clone)]
+pub struct ABM4 {
+    // Derivatives (velocities, accelerations) of previous steps, newest first
+    history: VecDeque<(DVector<Float>, DVector<Float>)>,
+
+    // Step size used for the history
+    dt: Option<Float>,
+
+    // Whether the last step was a multistep one, which leaves accelerations of its predicted state
+    predicted: bool,
+}
+
+// Adams-Bashforth-Moulton solver implementation (python)
+#[pymethods]
+impl ABM4 {
+    // Constructor
+    #[new]
+    pub fn new() -> Self {
+        ABM4 {
+            history: VecDeque::with_capacity(3),
+            dt: None,
+            predicted: false,
+        }
+    }
+}
+
+// Default implementation
+impl Default for ABM4 {
+    fn default() -> Self {
+        ABM4::new()
+    }
+}
+
+// Adams-Bashforth-Moulton solver implementation
+impl ODESolver for ABM4 {
+    fn solve(&mut self, dt: Float, positions: &mut DVector<Float>, velocities: &mut DVector<Float>, accelerations: &mut DVector<Float>, evaluate: &mut AccelerationFn) {
+        // History is only valid for the same step size
+        if self.dt != Some(dt) {
+            self.reset();
+            self.dt = Some(dt);
+        }
+
+        // Derivatives at the current state
+        let current = (velocities.clone(), accelerations.clone());
+
+        // Bootstrap history with RK4
+        if self.history.len() < 3 {
+            RK4.solve(dt, positions, velocities, accelerations, evaluate);
+            self.history.push_front(current);
+            self.predicted = false;
+            return;
+        }
+
+        // Predictor (Adams-Bashforth 4)
+        let (v1, a1) = &self.history[0];
+        let (v2, a2) = &self.history[1];
+        let (v3, a3) = &self.history[2];
+        let (v0, a0) = &current;
+        let predicted_positions = &*positions + (v0 * 55.0 - v1 * 59.0 + v2 * 37.0 - v3 * 9.0) * (dt / 24.0);
+        let predicted_velocities = &*velocities + (a0 * 55.0 - a1 * 59.0 + a2 * 37.0 - a3 * 9.0) * (dt / 24.0);
+
+        // Evaluate
+        evaluate(&predicted_positions, &predicted_velocities, accelerations);
+
+        // Corrector (Adams-Moulton 4)
+        *positions += (&predicted_velocities * 9.0 + v0 * 19.0 - v1 * 5.0 + v2) * (dt / 24.0);
+        *velocities += (&*accelerations * 9.0 + a0 * 19.0 - a1 * 5.0 + a2) * (dt / 24.0);
+
+        // Remember current derivatives
+        self.history.pop_back();
+        self.history.push_front(current);
+        self.predicted = true;
+    }
+
+    fn reset(&mut self) {
+        self.history.clear();
+        self.dt = None;
+        self.predicted = false;
+    }
+
+    // Accelerations of the predicted state can start the next step (PEC mode, one evaluation per step)
+    fn leaves_accelerations(&self) -> bool {
+        self.predicted
+    }
+
+    fn box_clone(&self) -> Option<Box<dyn Send + ODESolver>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+// Test module
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn abm4_ode_solver_harmonic_oscillator() {
+        // Create solver
+        let mut solver = ABM4::new();
+
+        // Damped harmonic oscillator (a = -x - 0.2v)
+        let mut positions = DVector::from_vec(vec![1.0]);
+        let mut velocities = DVector::from_vec(vec![0.0]);
+
+        // Solve until t = 10, counting evaluations
+        let dt = 0.05;
+        let mut evaluations = 0;
+        for _ in 0..200 {
+            let mut accelerations = -&positions - &velocities * 0.2;
+            solver.solve(dt, &mut positions, &mut velocities, &mut accelerations, &mut |p, v, a| {
+                evaluations += 1;
+                a.copy_from(&(-p - v * 0.2));
+            });
+        }
+
+        // Compare with analytical solution
+        let t: Float = 10.0;
+        let w = (1.0 as Float - 0.01).sqrt();
+        let expected = (-0.1 * t).exp() * ((w * t).cos() + 0.1 / w * (w * t).sin());
+        assert!((positions[0] - expected).abs() < 1e-4);
+
+        // One evaluation per step after the RK4 bootstrap
+        assert_eq!(evaluations, 3 * 3 + 197);
+    }
+
+    #[test]
+    fn abm4_ode_solver_reset() {
+        // Create solver
+        let mut solver = ABM4::new();
+        let mut positions = DVector::from_vec(vec![1.0]);
+        let mut velocities = DVector::from_vec(vec![0.0]);
+        let mut accelerations = DVector::from_vec(vec![-1.0]);
+
+        // Fill history
+        for _ in 0..5 {
+            solver.solve(0.1, &mut positions, &mut velocities, &mut accelerations, &mut |p, _, a| a.copy_from(&-p));
+        }
+        assert_eq!(solver.history.len(), 3);
+
+        // Reset and changing step size restart the bootstrap
+        solver.reset();
+        assert!(solver.history.is_empty());
+        solver.solve(0.1, &mut positions, &mut velocities, &mut accelerations, &mut |p, _, a| a.copy_from(&-p));
+        solver.solve(0.2, &mut positions, &mut velocities, &mut accelerations, &mut |p, _, a| a.copy_from(&-p));
+        assert_eq!(solver.history.len(), 1);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,186 @@
+// External imports
+use nalgebra::DVector;
+
+// PyO3 imports
+use pyo3::prelude::*;
+
+// Crate imports
+use crate::Float;
+use super::{ODESolver, AccelerationFn};
+
+// Boris pusher implementation (for charged particles in magnetic fields), velocities are kicked by the electric part
+// around an exact rotation by the magnetic part and positions then drift with the new velocities (first order,
+// like symplectic Euler, as positions and velocities aren't staggered by half a step)
+#[pyclass]
+#[derive(Clone)]
+pub struct BorisODE {
+    // Gyration frequencies (q * B / m) of each mass
+    gyrofrequencies: DVector<Float>,
+}
+
+// Boris pusher implementation (python)
+#[pymethods]
+impl BorisODE {
+    // Constructor
+    #[new]
+    pub fn new() -> Self {
+        BorisODE {
+            gyrofrequencies: DVector::zeros(0),
+        }
+    }
+}
+
+// Default implementation
+impl Default for BorisODE {
+    fn default() -> Self {
+        BorisODE::new()
+    }
+}
+
+// Boris pusher implementation
+impl ODESolver for BorisODE {
+    fn solve(&mut self, dt: Float, positions: &mut DVector<Float>, velocities: &mut DVector<Float>, accelerations: &mut DVector<Float>, _evaluate: &mut AccelerationFn) {
+        // Masses (first pairs of coordinates) gyrate with their frequencies
+        let masses = self.gyrofrequencies.len();
+        for i in 0..masses {
+            let w = self.gyrofrequencies[i];
+            let (mut vx, mut vy) = (velocities[i * 2], velocities[i * 2 + 1]);
+
+            // Remove magnetic part (w * (vy, -vx)) from accelerations
+            let ax = accelerations[i * 2] - w * vy;
+            let ay = accelerations[i * 2 + 1] + w * vx;
+
+            // Half kick
+            vx += 0.5 * dt * ax;
+            vy += 0.5 * dt * ay;
+
+            // Exact rotation around magnetic field by -w * dt
+            let (sin, cos) = (w * dt).sin_cos();
+            (vx, vy) = (cos * vx + sin * vy, cos * vy - sin * vx);
+
+            // Half kick
+            vx += 0.5 * dt * ax;
+            vy += 0.5 * dt * ay;
+
+            // Update velocities
+            velocities[i * 2] = vx;
+            velocities[i * 2 + 1] = vy;
+
+            // Drift with the new velocities
+            positions[i * 2] += vx * dt;
+            positions[i * 2 + 1] += vy * dt;
+        }
+
+        // Remaining coordinates (angles of rigid bodies) don't feel magnetic fields, each one gets a kick and a drift
+        for j in masses * 2..positions.len() {
+            velocities[j] += dt * accelerations[j];
+            positions[j] += velocities[j] * dt;
+        }
+    }
+
+    fn set_gyrofrequencies(&mut self, gyrofrequencies: &DVector<Float>) {
+        self.gyrofrequencies.clone_from(gyrofrequencies);
+    }
+
+    fn box_clone(&self) -> Option<Box<dyn Send + ODESolver>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+// Test module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::EulerODE;
+
+    // Largest deviation from the exact orbit (and final speed) while gyrating in constant field (w = 2)
+    fn gyration_error(solver: &mut dyn ODESolver) -> (Float, Float) {
+        // Circular orbit with radius 0.5 around origin
+        let w = 2.0;
+        let mut positions = DVector::from_vec(vec![0.0, 0.5]);
+        let mut velocities = DVector::from_vec(vec![1.0, 0.0]);
+        solver.set_gyrofrequencies(&DVector::from_vec(vec![w]));
+
+        let mut max_error: Float = 0.0;
+        for _ in 0..10000 {
+            let mut accelerations = DVector::from_vec(vec![w * velocities[1], -w * velocities[0]]);
+            solver.solve(0.01, &mut positions, &mut velocities, &mut accelerations, &mut |_, _, _| {});
+            max_error = max_error.max((positions.norm() - 0.5).abs());
+        }
+        (max_error, velocities.norm())
+    }
+
+    #[test]
+    fn boris_ode_solver_gyration() {
+        let (error, speed) = gyration_error(&mut BorisODE::new());
+
+        // Speed is preserved (up to rounding) and over 30 periods the orbit only stays shifted by half a step (v * dt / 2)
+        assert!((speed - 1.0).abs() < 1e-3);
+        assert!(error < 0.006, "orbit error {}", error);
+    }
+
+    #[test]
+    fn boris_ode_solver_beats_euler() {
+        // Explicit Euler spirals outward
+        let (error, _) = gyration_error(&mut EulerODE);
+        assert!(error > 0.5);
+    }
+
+    // Gyrating masses followed by angles of bodies spun up by a constant angular acceleration
+    fn masses_and_bodies(masses: usize, bodies: usize) {
+        let (w, dt, steps) = (2.0, 0.01, 1000);
+        let mut solver = BorisODE::new();
+        solver.set_gyrofrequencies(&DVector::from_element(masses, w));
+
+        // Masses on circular orbits with radius 0.5, bodies start at rest
+        let n = masses * 2 + bodies;
+        let mut positions = DVector::from_fn(n, |j, _| if j < masses * 2 && j % 2 == 1 { 0.5 } else { 0.0 });
+        let mut velocities = DVector::from_fn(n, |j, _| if j < masses * 2 && j % 2 == 0 { 1.0 } else { 0.0 });
+        for _ in 0..steps {
+            let mut accelerations = DVector::from_fn(n, |j, _| match j {
+                j if j >= masses * 2 => 0.5,
+                j if j % 2 == 0 => w * velocities[j + 1],
+                j => -w * velocities[j - 1],
+            });
+            solver.solve(dt, &mut positions, &mut velocities, &mut accelerations, &mut |_, _, _| {});
+        }
+
+        // Masses keep their speed and orbit (up to the shift by half a step)
+        for i in 0..masses {
+            let (position, velocity) = (positions.fixed_rows::<2>(i * 2), velocities.fixed_rows::<2>(i * 2));
+            assert!((velocity.norm() - 1.0).abs() < 1e-3);
+            assert!((position.norm() - 0.5).abs() < 0.006);
+        }
+
+        // Angles get plain kicks and drifts, sum of w_k * dt for w_k = 0.5 * k * dt
+        let expected = 0.5 * dt * dt * (steps * (steps + 1) / 2) as Float;
+        for j in masses * 2..n {
+            assert!((velocities[j] - 0.5 * steps as Float * dt).abs() < 1e-4);
+            assert!((positions[j] - expected).abs() < 1e-3, "angle {} expected {}", positions[j], expected);
+        }
+    }
+
+    #[test]
+    fn boris_ode_solver_with_odd_number_of_bodies() {
+        masses_and_bodies(2, 3);
+    }
+
+    #[test]
+    fn boris_ode_solver_with_even_number_of_bodies() {
+        masses_and_bodies(3, 2);
+    }
+
+    #[test]
+    fn boris_ode_solver_with_electric_field() {
+        // Without magnetic field Boris reduces to symplectic Euler (full kick, then drift with the new velocity)
+        let mut solver = BorisODE::new();
+        let mut positions = DVector::from_vec(vec![0.0, 0.0]);
+        let mut velocities = DVector::from_vec(vec![1.0, 1.0]);
+        let mut accelerations = DVector::from_vec(vec![2.0, 2.0]);
+
+        solver.solve(1.0, &mut positions, &mut velocities, &mut accelerations, &mut |_, _, _| {});
+
+        assert_eq!(velocities, DVector::from_vec(vec![3.0, 3.0]));
+        assert_eq!(positions, DVector::from_vec(vec![3.0, 3.0]));
+    }
+}
\ No newline at end of file
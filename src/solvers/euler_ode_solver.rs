@@ -6,20 +6,35 @@ use pyo3::prelude::*;
 
 // Crate imports
 use crate::Float;
-use super::ODESolver;
+use super::{ODESolver, AccelerationFn};
 
 // Euler solver implementation
 #[pyclass]
+#[derive(Clone)]
 pub struct EulerODE;
 
+// Euler solver implementation (python)
+#[pymethods]
+impl EulerODE {
+    // Constructor
+    #[new]
+    fn new() -> Self {
+        EulerODE
+    }
+}
+
 // Euler solver implementation
 impl ODESolver for EulerODE {
-    fn solve(&self, dt: Float, positions: &mut DVector<Float>, velocities: &mut DVector<Float>, accelerations: &mut DVector<Float>) {
+    fn solve(&mut self, dt: Float, positions: &mut DVector<Float>, velocities: &mut DVector<Float>, accelerations: &mut DVector<Float>, _evaluate: &mut AccelerationFn) {
         // Update positions
         *positions += velocities.clone() * dt;
         // Update velocities
         *velocities += accelerations.clone() * dt;
     }
+
+    fn box_clone(&self) -> Option<Box<dyn Send + ODESolver>> {
+        Some(Box::new(self.clone()))
+    }
 }
 
 // Test module
@@ -30,7 +45,7 @@ mod tests {
     #[test]
     fn euler_ode_solver() {
         // Create solver
-        let solver = EulerODE;
+        let mut solver = EulerODE;
 
         // Create vectors
         let mut positions = DVector::from_vec(vec![0.0, 0.0]);
@@ -38,7 +53,7 @@ mod tests {
         let mut accelerations = DVector::from_vec(vec![0.0, 0.0]);
 
         // Solve
-        solver.solve(1.0, &mut positions, &mut velocities, &mut accelerations);
+        solver.solve(1.0, &mut positions, &mut velocities, &mut accelerations, &mut |_, _, _| {});
 
         // Check results
         assert_eq!(positions, DVector::from_vec(vec![1.0, 1.0]));
@@ -49,7 +64,7 @@ mod tests {
     #[test]
     fn euler_ode_solver_with_acceleration() {
         // Create solver
-        let solver = EulerODE;
+        let mut solver = EulerODE;
 
         // Create vectors
         let mut positions = DVector::from_vec(vec![0.0, 0.0]);
@@ -57,7 +72,7 @@ mod tests {
         let mut accelerations = DVector::from_vec(vec![1.0, 1.0]);
 
         // Solve
-        solver.solve(1.0, &mut positions, &mut velocities, &mut accelerations);
+        solver.solve(1.0, &mut positions, &mut velocities, &mut accelerations, &mut |_, _, _| {});
 
         // Check results
         assert_eq!(positions, DVector::from_vec(vec![1.0, 1.0]));
@@ -76,7 +91,7 @@ mod bench {
     #[bench]
     fn euler_ode_solver(b: &mut Bencher) {
         // Create solver
-        let solver = EulerODE;
+        let mut solver = EulerODE;
 
         // Create vectors
         let mut positions = DVector::from_vec(vec![0.0, 0.0]);
@@ -86,7 +101,7 @@ mod bench {
         // Solve
         b.iter(|| {
             for _ in 0..20000 {
-                solver.solve(0.01, &mut positions, &mut velocities, &mut accelerations)
+                solver.solve(0.01, &mut positions, &mut velocities, &mut accelerations, &mut |_, _, _| {})
             }
         });
     }
@@ -0,0 +1,150 @@
+// External imports
+use nalgebra::{DVector, DMatrix};
+
+// PyO3 imports
+use pyo3::prelude::*;
+
+// Crate imports
+use crate::Float;
+use super::{ODESolver, AccelerationFn, JacobianFn, finite_difference_jacobian};
+
+// Implicit (backward) Euler solver implementation
+#[pyclass]
+#[derive(Clone)]
+pub struct ImplicitEulerODE {
+    // Newton iteration settings
+    #[pyo3(get)]
+    tolerance: Float,
+    #[pyo3(get)]
+    max_iterations: usize,
+}
+
+// Implicit Euler solver implementation (python)
+#[pymethods]
+impl ImplicitEulerODE {
+    // Constructor
+    #[new]
+    pub fn new(tolerance: Option<Float>, max_iterations: Option<usize>) -> Self {
+        ImplicitEulerODE {
+            tolerance: tolerance.unwrap_or(1e-5),
+            max_iterations: max_iterations.unwrap_or(10),
+        }
+    }
+}
+
+// Default implementation
+impl Default for ImplicitEulerODE {
+    fn default() -> Self {
+        ImplicitEulerODE::new(None, None)
+    }
+}
+
+// Internal implementation
+impl ImplicitEulerODE {
+    // Solve v1 = v0 + dt * a(x0 + dt * v1, v1) for v1 using Newton iterations
+    fn newton(&self, dt: Float, positions: &mut DVector<Float>, velocities: &mut DVector<Float>, accelerations: &mut DVector<Float>, evaluate: &mut AccelerationFn, mut jacobian: Option<&mut JacobianFn>) {
+        let n = positions.len();
+        let initial_positions = positions.clone();
+        let initial_velocities = velocities.clone();
+
+        // Current velocities are the initial guess (explicit Euler guess overshoots for stiff systems)
+        let mut da_dx = DMatrix::zeros(n, n);
+        let mut da_dv = DMatrix::zeros(n, n);
+        for _ in 0..self.max_iterations {
+            // Residual F(v1) = v1 - v0 - dt * a(x1, v1)
+            *positions = &initial_positions + &*velocities * dt;
+            evaluate(positions, velocities, accelerations);
+            let residual = &*velocities - &initial_velocities - &*accelerations * dt;
+
+            // Jacobian of the residual: I - dt^2 * da/dx - dt * da/dv
+            match jacobian.as_mut() {
+                Some(jacobian) => jacobian(positions, velocities, &mut da_dx, &mut da_dv),
+                None => finite_difference_jacobian(evaluate, positions, velocities, &mut da_dx, &mut da_dv),
+            }
+            let matrix = DMatrix::identity(n, n) - &da_dx * (dt * dt) - &da_dv * dt;
+
+            // Newton update (stop if the system is singular)
+            let delta = match matrix.lu().solve(&-residual) {
+                Some(delta) => delta,
+                None => break,
+            };
+            *velocities += &delta;
+
+            // Check convergence
+            if delta.amax() <= self.tolerance * (1.0 + velocities.amax()) {
+                break;
+            }
+        }
+
+        // Update positions and accelerations with the final velocities
+        *positions = &initial_positions + &*velocities * dt;
+        evaluate(positions, velocities, accelerations);
+    }
+}
+
+// Implicit Euler solver implementation
+impl ODESolver for ImplicitEulerODE {
+    fn solve(&mut self, dt: Float, positions: &mut DVector<Float>, velocities: &mut DVector<Float>, accelerations: &mut DVector<Float>, evaluate: &mut AccelerationFn) {
+        // Without jacobians fall back to finite differences
+        self.newton(dt, positions, velocities, accelerations, evaluate, None);
+    }
+
+    fn solve_with_jacobian(&mut self, dt: Float, positions: &mut DVector<Float>, velocities: &mut DVector<Float>, accelerations: &mut DVector<Float>, evaluate: &mut AccelerationFn, jacobian: &mut JacobianFn) {
+        self.newton(dt, positions, velocities, accelerations, evaluate, Some(jacobian));
+    }
+
+    fn box_clone(&self) -> Option<Box<dyn Send + ODESolver>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+// Test module
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn implicit_euler_ode_solver_linear_step() {
+        // Create solver
+        let mut solver = ImplicitEulerODE::default();
+
+        // Harmonic oscillator (a = -x)
+        let mut positions = DVector::from_vec(vec![1.0]);
+        let mut velocities = DVector::from_vec(vec![0.5]);
+        let mut accelerations = DVector::from_vec(vec![-1.0]);
+
+        // Solve with analytical jacobian
+        let dt = 0.5;
+        solver.solve_with_jacobian(dt, &mut positions, &mut velocities, &mut accelerations, &mut |p, _, a| a.copy_from(&-p), &mut |_, _, da_dx, da_dv| {
+            da_dx.fill(0.0);
+            da_dx.fill_diagonal(-1.0);
+            da_dv.fill(0.0);
+        });
+
+        // Compare with closed form v1 = (v0 - dt * x0) / (1 + dt^2)
+        let expected: Float = (0.5 - dt * 1.0) / (1.0 + dt * dt);
+        assert!((velocities[0] - expected).abs() < 1e-6);
+        assert!((positions[0] - (1.0 + dt * expected)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn implicit_euler_ode_solver_stiff_spring() {
+        // Create solvers
+        let mut solver = ImplicitEulerODE::default();
+
+        // Very stiff nonlinear spring (a = -k * x^3 - k * x)
+        let k = 1e4;
+        let mut positions = DVector::from_vec(vec![1.0, -0.5]);
+        let mut velocities = DVector::from_vec(vec![0.0, 0.0]);
+
+        // Solve with finite difference jacobians and large step
+        for _ in 0..100 {
+            let mut accelerations = positions.map(|x| -k * x * x * x - k * x);
+            solver.solve(0.1, &mut positions, &mut velocities, &mut accelerations, &mut |p, _, a| a.copy_from(&p.map(|x| -k * x * x * x - k * x)));
+        }
+
+        // Implicit Euler is stable and damps the oscillation
+        assert!(positions.amax() < 0.1);
+        assert!(positions.iter().all(|x| x.is_finite()));
+    }
+}
\ No newline at end of file
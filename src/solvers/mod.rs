@@ -1,27 +1,187 @@
 // External imports
-use nalgebra::DVector;
+use nalgebra::{DVector, DMatrix};
 
 // PyO3 imports
-use pyo3::prelude::*;
+use pyo3::{prelude::*, exceptions::{PyValueError, PyTypeError}};
 
 // Crate imports
 use crate::Float;
 
 // Import solvers
 mod euler_ode_solver;
+mod verlet_ode_solver;
+mod rk4_ode_solver;
+mod rk45_ode_solver;
+mod implicit_euler_ode_solver;
+mod symplectic_ode_solver;
+mod abm4_ode_solver;
+mod boris_ode_solver;
+mod python_ode_solver;
 pub use self::euler_ode_solver::EulerODE;
+pub use self::verlet_ode_solver::VerletODE;
+pub use self::rk4_ode_solver::RK4;
+pub use self::rk45_ode_solver::RK45;
+pub use self::implicit_euler_ode_solver::ImplicitEulerODE;
+pub use self::symplectic_ode_solver::{Yoshida4, Yoshida6, ForestRuth};
+pub use self::abm4_ode_solver::ABM4;
+pub use self::boris_ode_solver::BorisODE;
+pub use self::python_ode_solver::{ODESolverBase, StateBuffer, PythonODE};
+
+// Callback computing accelerations for given positions and velocities
+pub type AccelerationFn<'a> = dyn FnMut(&DVector<Float>, &DVector<Float>, &mut DVector<Float>) + 'a;
+
+// Callback computing jacobians of accelerations with respect to positions and velocities
+pub type JacobianFn<'a> = dyn FnMut(&DVector<Float>, &DVector<Float>, &mut DMatrix<Float>, &mut DMatrix<Float>) + 'a;
 
 // Trait for ODE solvers
+// Accelerations passed to solve are already evaluated for the current state,
+// evaluate can be used to get accelerations for any other state
 pub trait ODESolver {
-    fn solve(&self, dt: Float, positions: &mut DVector<Float>, velocities: &mut DVector<Float>, accelerations: &mut DVector<Float>);
+    fn solve(&mut self, dt: Float, positions: &mut DVector<Float>, velocities: &mut DVector<Float>, accelerations: &mut DVector<Float>, evaluate: &mut AccelerationFn);
+
+    // Solve with jacobians of accelerations available (only implicit solvers use them)
+    fn solve_with_jacobian(&mut self, dt: Float, positions: &mut DVector<Float>, velocities: &mut DVector<Float>, accelerations: &mut DVector<Float>, evaluate: &mut AccelerationFn, _jacobian: &mut JacobianFn) {
+        self.solve(dt, positions, velocities, accelerations, evaluate)
+    }
+
+    // Called before solve with magnetic gyration frequencies (q * B / m) of each mass
+    // Lorentz force of the magnetic field is already included in accelerations
+    fn set_gyrofrequencies(&mut self, _gyrofrequencies: &DVector<Float>) {}
+
+    // Called when masses are added or the state is overwritten (solvers with history should forget it)
+    fn reset(&mut self) {}
+
+    // Whether accelerations left by the last solve can start the next step, because they were evaluated at the new state
+    // (or close to it, like with half step velocities of velocity Verlet), otherwise they are evaluated again
+    fn leaves_accelerations(&self) -> bool {
+        false
+    }
+
+    // Accepted and rejected internal step counts (adaptive solvers only)
+    fn stats(&self) -> Option<(usize, usize)> {
+        None
+    }
+
+    // Copy of the solver for a cloned scene (solvers implemented in python can't be copied)
+    fn box_clone(&self) -> Option<Box<dyn Send + ODESolver>> {
+        None
+    }
+}
+
+// Names of solvers that can be selected with a string
+pub const SOLVER_NAMES: [&str; 10] = ["euler", "verlet", "rk4", "rk45", "implicit_euler", "yoshida4", "yoshida6", "forest_ruth", "abm4", "boris"];
+
+// Create solver from a name or a solver object
+pub(crate) fn solver_from_py(ode: &PyAny) -> PyResult<Box<dyn Send + ODESolver>> {
+    // Solver selected by name (with default parameters)
+    if let Ok(name) = ode.extract::<&str>() {
+        return match name {
+            "euler" => Ok(Box::new(EulerODE)),
+            "verlet" => Ok(Box::new(VerletODE)),
+            "rk4" => Ok(Box::new(RK4)),
+            "rk45" => Ok(Box::new(RK45::default())),
+            "implicit_euler" => Ok(Box::new(ImplicitEulerODE::default())),
+            "yoshida4" => Ok(Box::new(Yoshida4)),
+            "yoshida6" => Ok(Box::new(Yoshida6)),
+            "forest_ruth" => Ok(Box::new(ForestRuth)),
+            "abm4" => Ok(Box::new(ABM4::default())),
+            "boris" => Ok(Box::new(BorisODE::default())),
+            _ => Err(PyValueError::new_err(format!("Unknown ODE solver: {}, valid solvers are: {}", name, SOLVER_NAMES.join(", "))))
+        };
+    }
+
+    // Solver object (scene uses its own copy)
+    if let Ok(solver) = ode.extract::<PyRef<EulerODE>>() {
+        Ok(Box::new(solver.clone()))
+    } else if let Ok(solver) = ode.extract::<PyRef<VerletODE>>() {
+        Ok(Box::new(solver.clone()))
+    } else if let Ok(solver) = ode.extract::<PyRef<RK4>>() {
+        Ok(Box::new(solver.clone()))
+    } else if let Ok(solver) = ode.extract::<PyRef<RK45>>() {
+        Ok(Box::new(solver.clone()))
+    } else if let Ok(solver) = ode.extract::<PyRef<ImplicitEulerODE>>() {
+        Ok(Box::new(solver.clone()))
+    } else if let Ok(solver) = ode.extract::<PyRef<Yoshida4>>() {
+        Ok(Box::new(solver.clone()))
+    } else if let Ok(solver) = ode.extract::<PyRef<Yoshida6>>() {
+        Ok(Box::new(solver.clone()))
+    } else if let Ok(solver) = ode.extract::<PyRef<ForestRuth>>() {
+        Ok(Box::new(solver.clone()))
+    } else if let Ok(solver) = ode.extract::<PyRef<ABM4>>() {
+        Ok(Box::new(solver.clone()))
+    } else if let Ok(solver) = ode.extract::<PyRef<BorisODE>>() {
+        Ok(Box::new(solver.clone()))
+    } else if ode.extract::<PyRef<ODESolverBase>>().is_ok() {
+        // Solver implemented in python
+        Ok(Box::new(PythonODE::new(ode.into())))
+    } else {
+        Err(PyTypeError::new_err(format!("Expected solver name or solver object, got {}", ode.get_type().name()?)))
+    }
+}
+
+// Approximate jacobians of accelerations using forward differences
+pub(crate) fn finite_difference_jacobian(evaluate: &mut AccelerationFn, positions: &DVector<Float>, velocities: &DVector<Float>, da_dx: &mut DMatrix<Float>, da_dv: &mut DMatrix<Float>) {
+    let n = positions.len();
+
+    // Accelerations at the unperturbed state
+    let mut base = DVector::zeros(n);
+    evaluate(positions, velocities, &mut base);
+
+    // Perturb each position
+    let mut perturbed = DVector::zeros(n);
+    let mut state = positions.clone();
+    for j in 0..n {
+        let eps = Float::EPSILON.sqrt() * positions[j].abs().max(1.0);
+        state[j] += eps;
+        evaluate(&state, velocities, &mut perturbed);
+        state[j] = positions[j];
+        da_dx.set_column(j, &((&perturbed - &base) / eps));
+    }
+
+    // Perturb each velocity
+    let mut state = velocities.clone();
+    for j in 0..n {
+        let eps = Float::EPSILON.sqrt() * velocities[j].abs().max(1.0);
+        state[j] += eps;
+        evaluate(positions, &state, &mut perturbed);
+        state[j] = velocities[j];
+        da_dv.set_column(j, &((&perturbed - &base) / eps));
+    }
 }
 
 // Module with solvers
 #[pymodule]
 pub fn solvers(_py: Python, m: &PyModule) -> PyResult<()> {
+    // Base classes for python solvers
+    m.add_class::<ODESolverBase>()?;
+    m.add_class::<StateBuffer>()?;
+
     // Add Euler solver
     m.add_class::<EulerODE>()?;
 
+    // Add Verlet solver
+    m.add_class::<VerletODE>()?;
+
+    // Add Runge-Kutta solver
+    m.add_class::<RK4>()?;
+
+    // Add adaptive Dormand-Prince solver
+    m.add_class::<RK45>()?;
+
+    // Add implicit Euler solver
+    m.add_class::<ImplicitEulerODE>()?;
+
+    // Add symplectic composition solvers
+    m.add_class::<Yoshida4>()?;
+    m.add_class::<Yoshida6>()?;
+    m.add_class::<ForestRuth>()?;
+
+    // Add Adams-Bashforth-Moulton solver
+    m.add_class::<ABM4>()?;
+
+    // Add Boris pusher
+    m.add_class::<BorisODE>()?;
+
     // Return Ok(()) to indicate that initialization was successful
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,126 @@
+// External imports
+use nalgebra::DVector;
+
+// PyO3 imports
+use pyo3::{prelude::*, intern, exceptions::PyIndexError};
+
+// Crate imports
+use crate::Float;
+use super::{ODESolver, AccelerationFn};
+
+// Create ODE solver base class
+#[pyclass(name="ODESolver", subclass)]
+pub struct ODESolverBase;
+
+// ODE solver base class implementation
+#[pymethods]
+impl ODESolverBase {
+    // Constructor
+    #[new]
+    fn new() -> Self {
+        ODESolverBase
+    }
+
+    // Solve
+    fn solve(&self, _dt: Float, _positions: PyObject, _velocities: PyObject, _accelerations: PyObject) -> PyResult<()> {
+        // Panic if not implemented
+        panic!("ODE solver does not implement solve() method");
+    }
+
+    // Reset (called when masses are added or the state is overwritten)
+    fn reset(&self) {}
+
+    // __doc__ attribute
+    fn __doc__(&self) -> &'static str {
+        "ODE solver is a base class for solvers implemented in python"
+    }
+}
+
+// Buffer of scene state passed to python solvers (mutable in place)
+#[pyclass(name="StateBuffer")]
+pub struct StateBuffer {
+    data: DVector<Float>,
+}
+
+// StateBuffer implementation (python)
+#[pymethods]
+impl StateBuffer {
+    fn __len__(&self) -> usize {
+        self.data.len()
+    }
+
+    fn __getitem__(&self, index: isize) -> PyResult<Float> {
+        Ok(self.data[self.index(index)?])
+    }
+
+    fn __setitem__(&mut self, index: isize, value: Float) -> PyResult<()> {
+        let index = self.index(index)?;
+        self.data[index] = value;
+        Ok(())
+    }
+
+    // Copy of the buffer as a list
+    fn to_list(&self) -> Vec<Float> {
+        self.data.as_slice().to_vec()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("StateBuffer({:?})", self.data.as_slice())
+    }
+}
+
+// Internal implementation of StateBuffer
+impl StateBuffer {
+    // Convert python index (negative indices count from the end)
+    fn index(&self, index: isize) -> PyResult<usize> {
+        let len = self.data.len() as isize;
+        let index = if index < 0 { index + len } else { index };
+        if index < 0 || index >= len {
+            return Err(PyIndexError::new_err("StateBuffer index out of range"));
+        }
+        Ok(index as usize)
+    }
+}
+
+// Wrapper calling solver implemented in python
+pub struct PythonODE {
+    solver: PyObject,
+}
+
+// Constructor
+impl PythonODE {
+    pub fn new(solver: PyObject) -> Self {
+        PythonODE { solver }
+    }
+}
+
+// Python solver implementation
+impl ODESolver for PythonODE {
+    fn solve(&mut self, dt: Float, positions: &mut DVector<Float>, velocities: &mut DVector<Float>, accelerations: &mut DVector<Float>, _evaluate: &mut AccelerationFn) {
+        Python::with_gil(|py| {
+            // Create buffers
+            let buffers = [&*positions, &*velocities, &*accelerations]
+                .map(|data| Py::new(py, StateBuffer { data: data.clone() }).unwrap());
+
+            // Call python solver
+            let result = self.solver.call_method1(py, intern!(py, "solve"), (dt, &buffers[0], &buffers[1], &buffers[2]));
+            if result.is_err() {
+                panic!("Error while solving: {:?}", result);
+            }
+
+            // Copy buffers back
+            positions.copy_from(&buffers[0].borrow(py).data);
+            velocities.copy_from(&buffers[1].borrow(py).data);
+            accelerations.copy_from(&buffers[2].borrow(py).data);
+        });
+    }
+
+    fn reset(&mut self) {
+        Python::with_gil(|py| {
+            let result = self.solver.call_method0(py, intern!(py, "reset"));
+            if result.is_err() {
+                panic!("Error while resetting solver: {:?}", result);
+            }
+        });
+    }
+}
\ No newline at end of file
@@ -0,0 +1,211 @@
+// External imports
+use nalgebra::DVector;
+
+// PyO3 imports
+use pyo3::prelude::*;
+
+// Crate imports
+use crate::Float;
+use super::{ODESolver, AccelerationFn};
+
+// Dormand-Prince tableau
+const A: [[Float; 6]; 7] = [
+    [0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [1.0 / 5.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [3.0 / 40.0, 9.0 / 40.0, 0.0, 0.0, 0.0, 0.0],
+    [44.0 / 45.0, -56.0 / 15.0, 32.0 / 9.0, 0.0, 0.0, 0.0],
+    [19372.0 / 6561.0, -25360.0 / 2187.0, 64448.0 / 6561.0, -212.0 / 729.0, 0.0, 0.0],
+    [9017.0 / 3168.0, -355.0 / 33.0, 46732.0 / 5247.0, 49.0 / 176.0, -5103.0 / 18656.0, 0.0],
+    [35.0 / 384.0, 0.0, 500.0 / 1113.0, 125.0 / 192.0, -2187.0 / 6784.0, 11.0 / 84.0],
+];
+// Difference between 5th and 4th order weights
+const E: [Float; 7] = [71.0 / 57600.0, 0.0, -71.0 / 16695.0, 71.0 / 1920.0, -17253.0 / 339200.0, 22.0 / 525.0, -1.0 / 40.0];
+
+// Adaptive Dormand-Prince 5(4) solver implementation
+#[pyclass]
+#[derive(Clone)]
+pub struct RK45 {
+    // Tolerances
+    #[pyo3(get)]
+    rtol: Float,
+    #[pyo3(get)]
+    atol: Float,
+
+    // Last internal step size (reused in the next call)
+    step: Option<Float>,
+
+    // Statistics
+    accepted: usize,
+    rejected: usize,
+}
+
+// Dormand-Prince solver implementation (python)
+#[pymethods]
+impl RK45 {
+    // Constructor
+    #[new]
+    pub fn new(rtol: Option<Float>, atol: Option<Float>) -> Self {
+        RK45 {
+            rtol: rtol.unwrap_or(1e-3),
+            atol: atol.unwrap_or(1e-6),
+            step: None,
+            accepted: 0,
+            rejected: 0,
+        }
+    }
+}
+
+// Default implementation
+impl Default for RK45 {
+    fn default() -> Self {
+        RK45::new(None, None)
+    }
+}
+
+// Dormand-Prince solver implementation
+impl ODESolver for RK45 {
+    fn solve(&mut self, dt: Float, positions: &mut DVector<Float>, velocities: &mut DVector<Float>, accelerations: &mut DVector<Float>, evaluate: &mut AccelerationFn) {
+        let n = positions.len();
+
+        // Stage derivatives (of positions and velocities)
+        let mut kx: Vec<DVector<Float>> = vec![DVector::zeros(n); 7];
+        let mut kv: Vec<DVector<Float>> = vec![DVector::zeros(n); 7];
+        kv[0].copy_from(accelerations);
+
+        // Integrate over dt using adaptive internal steps
+        let mut time = 0.0;
+        let mut h = self.step.unwrap_or(dt).min(dt);
+        let min_step = dt * Float::EPSILON * 16.0;
+        while time < dt {
+            // Don't step past the end of the interval
+            let last = time + h >= dt;
+            if last {
+                h = dt - time;
+            }
+
+            // Evaluate stages
+            kx[0].copy_from(velocities);
+            let mut stage_positions = positions.clone();
+            let mut stage_velocities = velocities.clone();
+            for s in 1..7 {
+                stage_positions.copy_from(positions);
+                stage_velocities.copy_from(velocities);
+                for j in 0..s {
+                    if A[s][j] != 0.0 {
+                        stage_positions.axpy(h * A[s][j], &kx[j], 1.0);
+                        stage_velocities.axpy(h * A[s][j], &kv[j], 1.0);
+                    }
+                }
+                kx[s].copy_from(&stage_velocities);
+                evaluate(&stage_positions, &stage_velocities, &mut kv[s]);
+            }
+
+            // Estimate error (scaled RMS norm over positions and velocities)
+            let mut error = 0.0;
+            for i in 0..n {
+                let mut error_x = 0.0;
+                let mut error_v = 0.0;
+                for s in 0..7 {
+                    error_x += h * E[s] * kx[s][i];
+                    error_v += h * E[s] * kv[s][i];
+                }
+                let scale_x = self.atol + self.rtol * positions[i].abs().max(stage_positions[i].abs());
+                let scale_v = self.atol + self.rtol * velocities[i].abs().max(stage_velocities[i].abs());
+                error += (error_x / scale_x).powi(2) + (error_v / scale_v).powi(2);
+            }
+            let error = if n > 0 { (error / (2 * n) as Float).sqrt() } else { 0.0 };
+
+            // Accept or reject step
+            if error <= 1.0 || h <= min_step {
+                // Last stage is evaluated at the new state (first same as last)
+                time = if last { dt } else { time + h };
+                positions.copy_from(&stage_positions);
+                velocities.copy_from(&stage_velocities);
+                let (first, rest) = kv.split_at_mut(1);
+                first[0].copy_from(&rest[5]);
+                self.accepted += 1;
+
+                // Grow step size
+                let factor = if error == 0.0 { 5.0 } else { (0.9 * error.powf(-0.2)).clamp(0.2, 5.0) };
+                // Don't remember steps shortened only to hit the end of the interval
+                if !last || self.step.is_none() {
+                    self.step = Some(h * factor);
+                }
+                h *= factor;
+            } else {
+                // Shrink step size and try again
+                self.rejected += 1;
+                h *= (0.9 * error.powf(-0.2)).clamp(0.2, 1.0);
+                h = h.max(min_step);
+                self.step = Some(h);
+            }
+        }
+
+        // Store accelerations at the new state
+        accelerations.copy_from(&kv[0]);
+    }
+
+    fn reset(&mut self) {
+        // Previous step size may not fit the new state
+        self.step = None;
+    }
+
+    fn stats(&self) -> Option<(usize, usize)> {
+        Some((self.accepted, self.rejected))
+    }
+
+    fn box_clone(&self) -> Option<Box<dyn Send + ODESolver>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+// Test module
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rk45_ode_solver_harmonic_oscillator() {
+        // Create solver
+        let mut solver = RK45::new(Some(1e-5), Some(1e-5));
+
+        // Harmonic oscillator (a = -x)
+        let mut positions = DVector::from_vec(vec![1.0, 0.0]);
+        let mut velocities = DVector::from_vec(vec![0.0, 1.0]);
+        let mut accelerations = -positions.clone();
+
+        // Solve whole interval in one call
+        solver.solve(10.0, &mut positions, &mut velocities, &mut accelerations, &mut |p, _, a| a.copy_from(&-p));
+
+        // Compare with analytical solution
+        let t: Float = 10.0;
+        assert!((positions[0] - t.cos()).abs() < 1e-3);
+        assert!((positions[1] - t.sin()).abs() < 1e-3);
+        assert!((accelerations[0] + positions[0]).abs() < 1e-6);
+
+        // Initial step (whole interval) is too large and must be rejected
+        let (accepted, rejected) = solver.stats().unwrap();
+        assert!(accepted > 1);
+        assert!(rejected >= 1);
+    }
+
+    #[test]
+    fn rk45_ode_solver_adapts_step_size() {
+        // Create solvers
+        let mut loose = RK45::new(Some(1e-2), Some(1e-2));
+        let mut tight = RK45::new(Some(1e-6), Some(1e-6));
+
+        // Solve the same problem with both
+        for solver in [&mut loose, &mut tight] {
+            let mut positions = DVector::from_vec(vec![1.0]);
+            let mut velocities = DVector::from_vec(vec![0.0]);
+            let mut accelerations = DVector::from_vec(vec![-1.0]);
+            for _ in 0..10 {
+                solver.solve(1.0, &mut positions, &mut velocities, &mut accelerations, &mut |p, _, a| a.copy_from(&-p));
+            }
+        }
+
+        // Tighter tolerance needs more steps
+        assert!(tight.stats().unwrap().0 > loose.stats().unwrap().0);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,104 @@
+// External imports
+use nalgebra::DVector;
+
+// PyO3 imports
+use pyo3::prelude::*;
+
+// Crate imports
+use crate::Float;
+use super::{ODESolver, AccelerationFn};
+
+// Classic fourth-order Runge-Kutta solver implementation
+#[pyclass]
+#[derive(Clone)]
+pub struct RK4;
+
+// Classic fourth-order Runge-Kutta solver implementation (python)
+#[pymethods]
+impl RK4 {
+    // Constructor
+    #[new]
+    fn new() -> Self {
+        RK4
+    }
+}
+
+// Classic fourth-order Runge-Kutta solver implementation
+impl ODESolver for RK4 {
+    fn solve(&mut self, dt: Float, positions: &mut DVector<Float>, velocities: &mut DVector<Float>, accelerations: &mut DVector<Float>, evaluate: &mut AccelerationFn) {
+        // First stage (accelerations are already evaluated)
+        let k1x = velocities.clone();
+        let k1v = accelerations.clone();
+
+        // Second stage
+        let k2x = &*velocities + &k1v * (0.5 * dt);
+        let mut k2v = DVector::zeros(accelerations.len());
+        evaluate(&(&*positions + &k1x * (0.5 * dt)), &k2x, &mut k2v);
+
+        // Third stage
+        let k3x = &*velocities + &k2v * (0.5 * dt);
+        let mut k3v = DVector::zeros(accelerations.len());
+        evaluate(&(&*positions + &k2x * (0.5 * dt)), &k3x, &mut k3v);
+
+        // Fourth stage
+        let k4x = &*velocities + &k3v * dt;
+        let mut k4v = DVector::zeros(accelerations.len());
+        evaluate(&(&*positions + &k3x * dt), &k4x, &mut k4v);
+
+        // Update positions
+        *positions += (k1x + k2x * 2.0 + k3x * 2.0 + k4x) * (dt / 6.0);
+        // Update velocities
+        *velocities += (k1v + k2v * 2.0 + k3v * 2.0 + k4v) * (dt / 6.0);
+    }
+
+    fn box_clone(&self) -> Option<Box<dyn Send + ODESolver>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+// Test module
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rk4_ode_solver_with_acceleration() {
+        // Create solver
+        let mut solver = RK4;
+
+        // Create vectors
+        let mut positions = DVector::from_vec(vec![0.0, 0.0]);
+        let mut velocities = DVector::from_vec(vec![1.0, 1.0]);
+        let mut accelerations = DVector::from_vec(vec![2.0, 2.0]);
+
+        // Solve (constant acceleration)
+        solver.solve(1.0, &mut positions, &mut velocities, &mut accelerations, &mut |_, _, a| a.fill(2.0));
+
+        // Check results (exact for constant acceleration)
+        assert_eq!(positions, DVector::from_vec(vec![2.0, 2.0]));
+        assert_eq!(velocities, DVector::from_vec(vec![3.0, 3.0]));
+    }
+
+    #[test]
+    fn rk4_ode_solver_harmonic_oscillator() {
+        // Create solver
+        let mut solver = RK4;
+
+        // Damped harmonic oscillator (a = -x - 0.2v), uses velocity dependent stages
+        let mut positions = DVector::from_vec(vec![1.0]);
+        let mut velocities = DVector::from_vec(vec![0.0]);
+
+        // Solve until t = 10
+        let dt = 0.05;
+        for _ in 0..200 {
+            let mut accelerations = -&positions - &velocities * 0.2;
+            solver.solve(dt, &mut positions, &mut velocities, &mut accelerations, &mut |p, v, a| a.copy_from(&(-p - v * 0.2)));
+        }
+
+        // Compare with analytical solution
+        let t: Float = 10.0;
+        let w = (1.0 as Float - 0.01).sqrt();
+        let expected = (-0.1 * t).exp() * ((w * t).cos() + 0.1 / w * (w * t).sin());
+        assert!((positions[0] - expected).abs() < 1e-4);
+    }
+}
\ No newline at end of file
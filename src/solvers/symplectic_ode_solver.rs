@@ -0,0 +1,199 @@
+// External imports
+use nalgebra::DVector;
+
+// PyO3 imports
+use pyo3::prelude::*;
+
+// Crate imports
+use crate::Float;
+use super::{ODESolver, AccelerationFn};
+
+// Triple jump weight (1 / (2 - 2^(1/3))), used by Yoshida 4th order and Forest-Ruth
+const TRIPLE_JUMP: f64 = 1.351_207_191_959_657_8;
+
+// Yoshida 4th order weights
+const YOSHIDA4: [f64; 3] = [TRIPLE_JUMP, 1.0 - 2.0 * TRIPLE_JUMP, TRIPLE_JUMP];
+
+// Yoshida 6th order weights (solution A)
+const YOSHIDA6: [f64; 7] = [
+    0.784_513_610_477_560, 0.235_573_213_359_357, -1.177_679_984_178_87, 1.315_186_320_683_906,
+    -1.177_679_984_178_87, 0.235_573_213_359_357, 0.784_513_610_477_560,
+];
+
+// Composition of velocity Verlet steps with given weights
+// Accelerations must be evaluated for the current state and are left evaluated for the new state
+fn compose(weights: &[f64], dt: Float, positions: &mut DVector<Float>, velocities: &mut DVector<Float>, accelerations: &mut DVector<Float>, evaluate: &mut AccelerationFn) {
+    for weight in weights {
+        let h = *weight as Float * dt;
+
+        // Kick
+        velocities.axpy(0.5 * h, accelerations, 1.0);
+        // Drift
+        positions.axpy(h, velocities, 1.0);
+        // Kick with accelerations at new positions
+        evaluate(positions, velocities, accelerations);
+        velocities.axpy(0.5 * h, accelerations, 1.0);
+    }
+}
+
+// Yoshida 4th order solver implementation
+#[pyclass]
+#[derive(Clone)]
+pub struct Yoshida4;
+
+// Yoshida 4th order solver implementation (python)
+#[pymethods]
+impl Yoshida4 {
+    // Constructor
+    #[new]
+    fn new() -> Self {
+        Yoshida4
+    }
+}
+
+// Yoshida 4th order solver implementation
+impl ODESolver for Yoshida4 {
+    fn solve(&mut self, dt: Float, positions: &mut DVector<Float>, velocities: &mut DVector<Float>, accelerations: &mut DVector<Float>, evaluate: &mut AccelerationFn) {
+        compose(&YOSHIDA4, dt, positions, velocities, accelerations, evaluate);
+    }
+
+    fn leaves_accelerations(&self) -> bool {
+        true
+    }
+
+    fn box_clone(&self) -> Option<Box<dyn Send + ODESolver>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+// Yoshida 6th order solver implementation
+#[pyclass]
+#[derive(Clone)]
+pub struct Yoshida6;
+
+// Yoshida 6th order solver implementation (python)
+#[pymethods]
+impl Yoshida6 {
+    // Constructor
+    #[new]
+    fn new() -> Self {
+        Yoshida6
+    }
+}
+
+// Yoshida 6th order solver implementation
+impl ODESolver for Yoshida6 {
+    fn solve(&mut self, dt: Float, positions: &mut DVector<Float>, velocities: &mut DVector<Float>, accelerations: &mut DVector<Float>, evaluate: &mut AccelerationFn) {
+        compose(&YOSHIDA6, dt, positions, velocities, accelerations, evaluate);
+    }
+
+    fn leaves_accelerations(&self) -> bool {
+        true
+    }
+
+    fn box_clone(&self) -> Option<Box<dyn Send + ODESolver>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+// Forest-Ruth solver implementation (position first variant of the 4th order composition)
+#[pyclass]
+#[derive(Clone)]
+pub struct ForestRuth;
+
+// Forest-Ruth solver implementation (python)
+#[pymethods]
+impl ForestRuth {
+    // Constructor
+    #[new]
+    fn new() -> Self {
+        ForestRuth
+    }
+}
+
+// Forest-Ruth solver implementation
+impl ODESolver for ForestRuth {
+    fn solve(&mut self, dt: Float, positions: &mut DVector<Float>, velocities: &mut DVector<Float>, accelerations: &mut DVector<Float>, evaluate: &mut AccelerationFn) {
+        let theta = TRIPLE_JUMP as Float;
+        let drifts = [theta * 0.5, (1.0 - theta) * 0.5, (1.0 - theta) * 0.5, theta * 0.5];
+        let kicks = [theta, 1.0 - 2.0 * theta, theta];
+
+        // Drift, kick, drift, kick, drift, kick, drift
+        for (i, drift) in drifts.iter().enumerate() {
+            positions.axpy(drift * dt, velocities, 1.0);
+            if let Some(kick) = kicks.get(i) {
+                evaluate(positions, velocities, accelerations);
+                velocities.axpy(kick * dt, accelerations, 1.0);
+            }
+        }
+
+        // Leave accelerations evaluated for the new state
+        evaluate(positions, velocities, accelerations);
+    }
+
+    fn leaves_accelerations(&self) -> bool {
+        true
+    }
+
+    fn box_clone(&self) -> Option<Box<dyn Send + ODESolver>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+// Test module
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Accelerations of a body orbiting a fixed center (GM = 1)
+    fn kepler(positions: &DVector<Float>, _velocities: &DVector<Float>, accelerations: &mut DVector<Float>) {
+        let r = positions.norm();
+        accelerations.copy_from(&(-positions / (r * r * r)));
+    }
+
+    // Energy of a body orbiting a fixed center (GM = 1)
+    fn kepler_energy(positions: &DVector<Float>, velocities: &DVector<Float>) -> Float {
+        0.5 * velocities.norm_squared() - 1.0 / positions.norm()
+    }
+
+    // Maximum relative energy error over 100 eccentric (e = 0.5) orbits
+    fn kepler_energy_error(solver: &mut dyn ODESolver) -> Float {
+        // Start at periapsis of orbit with semi-major axis 1
+        let mut positions = DVector::from_vec(vec![0.5, 0.0]);
+        let mut velocities = DVector::from_vec(vec![0.0, (3.0 as Float).sqrt()]);
+        let mut accelerations = DVector::zeros(2);
+        kepler(&positions, &velocities, &mut accelerations);
+        let initial_energy = kepler_energy(&positions, &velocities);
+
+        // Period is 2 * pi
+        let steps = 200;
+        let dt = 2.0 * std::f64::consts::PI as Float / steps as Float;
+        let mut max_error: Float = 0.0;
+        for _ in 0..100 * steps {
+            solver.solve(dt, &mut positions, &mut velocities, &mut accelerations, &mut kepler);
+            max_error = max_error.max(((kepler_energy(&positions, &velocities) - initial_energy) / initial_energy).abs());
+        }
+        max_error
+    }
+
+    #[test]
+    fn yoshida4_ode_solver_kepler_energy() {
+        assert!(kepler_energy_error(&mut Yoshida4) < 1e-3);
+    }
+
+    #[test]
+    fn yoshida6_ode_solver_kepler_energy() {
+        assert!(kepler_energy_error(&mut Yoshida6) < 1e-3);
+    }
+
+    #[test]
+    fn forest_ruth_ode_solver_kepler_energy() {
+        assert!(kepler_energy_error(&mut ForestRuth) < 1e-3);
+    }
+
+    #[test]
+    fn symplectic_ode_solvers_beat_euler() {
+        // Explicit Euler drifts away over the same run
+        assert!(kepler_energy_error(&mut super::super::EulerODE) > 1e-1);
+    }
+}
\ No newline at end of file
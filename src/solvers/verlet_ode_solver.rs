@@ -0,0 +1,97 @@
+// External imports
+use nalgebra::DVector;
+
+// PyO3 imports
+use pyo3::prelude::*;
+
+// Crate imports
+use crate::Float;
+use super::{ODESolver, AccelerationFn};
+
+// Velocity Verlet solver implementation
+#[pyclass]
+#[derive(Clone)]
+pub struct VerletODE;
+
+// Velocity Verlet solver implementation (python)
+#[pymethods]
+impl VerletODE {
+    // Constructor
+    #[new]
+    fn new() -> Self {
+        VerletODE
+    }
+}
+
+// Velocity Verlet solver implementation
+impl ODESolver for VerletODE {
+    fn solve(&mut self, dt: Float, positions: &mut DVector<Float>, velocities: &mut DVector<Float>, accelerations: &mut DVector<Float>, evaluate: &mut AccelerationFn) {
+        // Update positions
+        *positions += velocities.clone() * dt + accelerations.clone() * (0.5 * dt * dt);
+
+        // Evaluate accelerations at new positions (with half step velocities for velocity dependent forces)
+        let half_velocities = velocities.clone() + accelerations.clone() * (0.5 * dt);
+        let mut new_accelerations = DVector::zeros(accelerations.len());
+        evaluate(positions, &half_velocities, &mut new_accelerations);
+
+        // Update velocities using average of old and new accelerations
+        *velocities += (accelerations.clone() + &new_accelerations) * (0.5 * dt);
+
+        // Store new accelerations
+        *accelerations = new_accelerations;
+    }
+
+    fn leaves_accelerations(&self) -> bool {
+        true
+    }
+
+    fn box_clone(&self) -> Option<Box<dyn Send + ODESolver>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+// Test module
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verlet_ode_solver_with_acceleration() {
+        // Create solver
+        let mut solver = VerletODE;
+
+        // Create vectors
+        let mut positions = DVector::from_vec(vec![0.0, 0.0]);
+        let mut velocities = DVector::from_vec(vec![1.0, 1.0]);
+        let mut accelerations = DVector::from_vec(vec![2.0, 2.0]);
+
+        // Solve (constant acceleration)
+        solver.solve(1.0, &mut positions, &mut velocities, &mut accelerations, &mut |_, _, a| a.fill(2.0));
+
+        // Check results (exact for constant acceleration)
+        assert_eq!(positions, DVector::from_vec(vec![2.0, 2.0]));
+        assert_eq!(velocities, DVector::from_vec(vec![3.0, 3.0]));
+        assert_eq!(accelerations, DVector::from_vec(vec![2.0, 2.0]));
+    }
+
+    #[test]
+    fn verlet_ode_solver_energy_conservation() {
+        // Create solver
+        let mut solver = VerletODE;
+
+        // Harmonic oscillator (a = -x)
+        let mut positions = DVector::from_vec(vec![1.0, 0.0]);
+        let mut velocities = DVector::from_vec(vec![0.0, 1.0]);
+        let mut accelerations = -positions.clone();
+        let energy = |p: &DVector<Float>, v: &DVector<Float>| 0.5 * (p.norm_squared() + v.norm_squared());
+        let initial_energy = energy(&positions, &velocities);
+
+        // Solve
+        for _ in 0..10000 {
+            solver.solve(0.01, &mut positions, &mut velocities, &mut accelerations, &mut |p, _, a| a.copy_from(&-p));
+        }
+
+        // Check energy stays bounded
+        assert!((energy(&positions, &velocities) - initial_energy).abs() < 1e-3);
+    }
+}
\ No newline at end of file